@@ -0,0 +1,3 @@
+pub use balatro_core as algorithm;
+#[cfg(feature = "serve")]
+pub mod server;
@@ -0,0 +1,322 @@
+use balatro_cli::algorithm::balatro_predictor::PredictionBackend;
+use balatro_cli::algorithm::card_data::CardData;
+use balatro_cli::algorithm::deck::{Deck, DeckChange};
+use balatro_cli::algorithm::distribution::OutcomeDistribution;
+use balatro_cli::algorithm::hand_rank::HandRank;
+use balatro_cli::algorithm::joker::Joker;
+use balatro_cli::algorithm::monte_carlo::{self, MonteCarloConfig};
+use balatro_cli::algorithm::notation::{format_cards, Hand};
+use balatro_cli::algorithm::optimizer::DiscardOption;
+use balatro_cli::algorithm::rules::RuleSet;
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+
+/// Predicts how a hand finishes and which cards to throw away, without the GUI.
+///
+/// Cards are written as a face followed by a suit, such as `Ah`, `10s`, `Td` or `K♥`, and
+/// separated by spaces or commas.
+#[derive(Parser)]
+#[command(name = "balatro-cli", version)]
+struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Expected score of the finished hand
+    Predict {
+        #[command(flatten)]
+        round: Round,
+        /// Cards to throw away from the hand before drawing
+        #[arg(long, default_value = "")]
+        discard: Hand,
+        #[command(flatten)]
+        backend: Backend,
+    },
+    /// Probability of finishing with each hand type
+    Distribution {
+        #[command(flatten)]
+        round: Round,
+        /// Cards to throw away from the hand before drawing
+        #[arg(long, default_value = "")]
+        discard: Hand,
+        #[command(flatten)]
+        backend: Backend,
+    },
+    /// Ranks every discard from the hand, best first
+    OptimizeDiscard {
+        #[command(flatten)]
+        round: Round,
+        /// Discards left this round, counting the one being chosen
+        #[arg(long, default_value_t = 1)]
+        discards_remaining: u8,
+        /// Only show the best options
+        #[arg(long)]
+        top: Option<usize>,
+        #[command(flatten)]
+        backend: Backend,
+    },
+    /// Samples random draws and reports every number with a 95% confidence interval
+    Simulate {
+        #[command(flatten)]
+        round: Round,
+        /// Cards to throw away from the hand before drawing
+        #[arg(long, default_value = "")]
+        discard: Hand,
+        /// Discards played after this one
+        #[arg(long, default_value_t = 0)]
+        discards_after: u8,
+        #[command(flatten)]
+        config: Sampling,
+    },
+    /// Serves the predictor as an HTTP JSON API, described at /openapi.json
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Address to listen on; keep it on localhost unless the API should be reachable
+        /// from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        host: std::net::IpAddr,
+        #[arg(long, default_value_t = 8787)]
+        port: u16,
+    },
+}
+
+#[derive(Args)]
+struct Round {
+    /// The cards in hand, such as "Ah Kd 10s 4c 4h"
+    #[arg(required = true)]
+    hand: Vec<Hand>,
+    /// Cards thrown away earlier in the round
+    #[arg(long, default_value = "")]
+    discarded: Hand,
+    /// The joker being played, such as WANDA
+    #[arg(long, value_parser = parse_joker)]
+    joker: Option<Joker>,
+    /// A change to the standard deck, such as "remove 2", "remove h", "remove As" or
+    /// "add 3 As"; repeat to apply several
+    #[arg(long = "deck", value_parser = parse_deck_change)]
+    deck_changes: Vec<DeckChange>,
+    /// Rules to play by: a preset (dst or balatro) or a .toml or .json file
+    #[arg(long, value_parser = parse_rules, default_value = "dst")]
+    rules: RuleSet,
+}
+
+#[derive(Args)]
+struct Backend {
+    /// Sample draws instead of enumerating every one
+    #[arg(long)]
+    monte_carlo: bool,
+    #[command(flatten)]
+    sampling: Sampling,
+}
+
+#[derive(Args)]
+struct Sampling {
+    /// Seed for the shuffles
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Most draws to sample
+    #[arg(long, default_value_t = MonteCarloConfig::default().max_samples)]
+    samples: u64,
+    /// Stop once every 95% interval is narrower than this on either side
+    #[arg(long)]
+    tolerance: Option<f64>,
+}
+
+impl Sampling {
+    fn config(&self) -> MonteCarloConfig {
+        MonteCarloConfig {
+            seed: self.seed,
+            max_samples: self.samples,
+            tolerance: self.tolerance,
+            ..MonteCarloConfig::default()
+        }
+    }
+}
+
+impl Backend {
+    fn backend(&self) -> PredictionBackend {
+        if self.monte_carlo {
+            PredictionBackend::MonteCarlo(self.sampling.config())
+        } else {
+            PredictionBackend::Exact
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = run(cli) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+impl Round {
+    fn hand(&self) -> Vec<CardData> {
+        self.hand.iter().flat_map(|cards| cards.iter().copied()).collect()
+    }
+
+    fn discarded(&self) -> Option<Vec<CardData>> {
+        optional(self.discarded.to_vec())
+    }
+
+    fn deck(&self) -> Deck {
+        Deck::from_changes(&self.deck_changes)
+    }
+}
+
+fn run(cli: Cli) -> anyhow::Result<()> {
+    match cli.command {
+        Command::Predict {
+            round,
+            discard,
+            backend,
+        } => {
+            let expected_score = backend
+                .backend()
+                .predictor(round.hand(), round.deck(), round.rules.clone())
+                .calculate_expected_score(
+                    round.discarded(),
+                    optional(discard.0),
+                    round.joker,
+                )?;
+            print(cli.json, &expected_score, || {
+                println!("Expected score: {:.1}", expected_score)
+            })
+        }
+        Command::Distribution {
+            round,
+            discard,
+            backend,
+        } => {
+            let distribution = backend
+                .backend()
+                .predictor(round.hand(), round.deck(), round.rules.clone())
+                .calculate_outcome_distribution(round.discarded(), optional(discard.0))?;
+            print(cli.json, &distribution, || print_distribution(&distribution))
+        }
+        Command::OptimizeDiscard {
+            round,
+            discards_remaining,
+            top,
+            backend,
+        } => {
+            let mut predictor = backend.backend().predictor(round.hand(), round.deck(), round.rules.clone());
+            let mut options = if discards_remaining == 1 {
+                predictor.optimize_discard(round.discarded(), round.joker)?
+            } else {
+                predictor.plan_discards(round.discarded(), discards_remaining, round.joker)?
+            };
+            options.truncate(top.unwrap_or(options.len()));
+            print(cli.json, &options, || print_options(&options))
+        }
+        Command::Simulate {
+            round,
+            discard,
+            discards_after,
+            config,
+        } => {
+            let outcome = monte_carlo::simulate(
+                &round.hand(),
+                &round.discarded,
+                &discard,
+                discards_after,
+                &round.deck(),
+                round.joker,
+                &config.config(),
+                &round.rules,
+            )?;
+            print(cli.json, &outcome, || {
+                println!("Samples: {}", outcome.samples);
+                for (rank, estimate) in &outcome.probabilities {
+                    println!(
+                        "{:<16}{:>7.2}%  ({:.2}% - {:.2}%)",
+                        rank_name(*rank),
+                        estimate.mean * 100.0,
+                        estimate.lower * 100.0,
+                        estimate.upper * 100.0
+                    );
+                }
+                println!(
+                    "Expected score: {:.1}  ({:.1} - {:.1})",
+                    outcome.expected_score.mean, outcome.expected_score.lower, outcome.expected_score.upper
+                );
+            })
+        }
+        #[cfg(feature = "serve")]
+        Command::Serve { host, port } => {
+            let address = std::net::SocketAddr::new(host, port);
+            tokio::runtime::Runtime::new()?.block_on(balatro_cli::server::serve(address))
+        }
+    }
+}
+
+fn optional(cards: Vec<CardData>) -> Option<Vec<CardData>> {
+    if cards.is_empty() { None } else { Some(cards) }
+}
+
+fn print<T: Serialize>(json: bool, value: &T, text: impl FnOnce()) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+    } else {
+        text();
+    }
+    Ok(())
+}
+
+fn print_distribution(distribution: &OutcomeDistribution) {
+    for (rank, probability) in &distribution.probabilities {
+        println!("{:<16}{:>7.2}%", rank_name(*rank), probability * 100.0);
+    }
+}
+
+fn print_options(options: &[DiscardOption]) {
+    for option in options {
+        let discard = if option.discard.is_empty() {
+            "nothing".to_string()
+        } else {
+            format_cards(&option.discard)
+        };
+        match option.samples {
+            Some(samples) => println!("{:>8.1}  discard {}  (sampled over {} draws)", option.expected_score, discard, samples),
+            None => println!("{:>8.1}  discard {}", option.expected_score, discard),
+        }
+    }
+}
+
+fn rank_name(rank: HandRank) -> &'static str {
+    match rank {
+        HandRank::HighCard => "High Card",
+        HandRank::Pair => "Pair",
+        HandRank::TwoPair => "Two Pair",
+        HandRank::ThreeOfAKind => "Three of a Kind",
+        HandRank::Straight => "Straight",
+        HandRank::Flush => "Flush",
+        HandRank::FullHouse => "Full House",
+        HandRank::FourOfAKind => "Four of a Kind",
+        HandRank::StraightFlush => "Straight Flush",
+        HandRank::RoyalFlush => "Royal Flush",
+    }
+}
+
+fn parse_rules(name: &str) -> Result<RuleSet, String> {
+    match RuleSet::preset(name) {
+        Some(rules) => Ok(rules),
+        None => RuleSet::load(name).map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_deck_change(text: &str) -> Result<DeckChange, String> {
+    text.parse().map_err(|e: anyhow::Error| e.to_string())
+}
+
+fn parse_joker(name: &str) -> Result<Joker, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_uppercase()))
+        .map_err(|_| format!("unknown joker {:?}", name))
+}
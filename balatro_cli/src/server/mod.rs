@@ -0,0 +1,320 @@
+use crate::algorithm::balatro_predictor::PredictionBackend;
+use crate::algorithm::card_data::CardData;
+use crate::algorithm::deck::Deck;
+use crate::algorithm::distribution::OutcomeDistribution;
+use crate::algorithm::error::PredictorError;
+use crate::algorithm::hand_rank::HandRank;
+use crate::algorithm::joker::Joker;
+use crate::algorithm::optimizer::DiscardOption;
+use crate::algorithm::planner::{exact_work, WORK_LIMIT};
+use crate::algorithm::rules::RuleSet;
+use axum::extract::rejection::{JsonRejection, PathRejection};
+use axum::extract::Path;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// OpenAPI description of every route, served at `/openapi.json`.
+pub const OPENAPI: &str = include_str!("openapi.json");
+
+/// Largest `rules.hand_size` a request may ask for, that of the Balatro rules.
+pub const MAX_HAND_SIZE: usize = RuleSet::BALATRO.hand_size;
+
+/// Most `discards_remaining` a request may plan for, that of the Balatro rules.
+pub const MAX_DISCARDS_REMAINING: u8 = RuleSet::BALATRO.discards;
+
+/// Most `max_samples` a Monte Carlo request may draw.
+pub const MAX_SAMPLES: u64 = 1_000_000;
+
+/// Most `inner_samples` a Monte Carlo request may compare later discards over.
+pub const MAX_INNER_SAMPLES: u32 = 1_024;
+
+/// Most hands an exact prediction may score, enough to rank every discard of a full
+/// Balatro hand but not to enumerate eight cards from a fresh deck.
+pub const MAX_WORK: u64 = 10 * WORK_LIMIT;
+
+/// How long a prediction may run before the request is answered with a timeout.
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The round a request asks about, with the same fields as the Tauri commands.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PredictRequest {
+    pub hand: Vec<CardData>,
+    #[serde(default)]
+    pub discarded_cards: Vec<CardData>,
+    #[serde(default)]
+    pub discard: Vec<CardData>,
+    #[serde(default)]
+    pub joker: Option<Joker>,
+    #[serde(default)]
+    pub backend: Option<PredictionBackend>,
+    #[serde(default)]
+    pub deck: Option<Deck>,
+    #[serde(default)]
+    pub rules: Option<RuleSet>,
+    /// Discards left this round, counting the one being chosen. Only used by the optimizer.
+    #[serde(default)]
+    pub discards_remaining: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Probability {
+    pub rank: HandRank,
+    pub probability: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Health {
+    pub status: String,
+    pub version: String,
+}
+
+/// An error response, shaped like a serialized [`PredictorError`].
+#[derive(Serialize, Debug)]
+pub struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    cards: Vec<CardData>,
+}
+
+impl From<PredictorError> for ApiError {
+    fn from(error: PredictorError) -> Self {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            code: error.code(),
+            message: error.to_string(),
+            cards: error.cards().to_vec(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        ApiError::from(PredictorError::from(error))
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError {
+            status: rejection.status(),
+            code: "invalid_request",
+            message: rejection.body_text(),
+            cards: Vec::new(),
+        }
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError {
+            status: StatusCode::NOT_FOUND,
+            code: "invalid_request",
+            message: rejection.body_text(),
+            cards: Vec::new(),
+        }
+    }
+}
+
+impl ApiError {
+    fn invalid_request(message: String) -> Self {
+        ApiError {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            code: "invalid_request",
+            message,
+            cards: Vec::new(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self)).into_response()
+    }
+}
+
+impl PredictRequest {
+    fn optional(cards: &[CardData]) -> Option<Vec<CardData>> {
+        if cards.is_empty() { None } else { Some(cards.to_vec()) }
+    }
+
+    /// Rejects requests that would keep the server busy for too long.
+    fn check_limits(&self) -> Result<(), ApiError> {
+        if let Some(rules) = &self.rules
+            && rules.hand_size > MAX_HAND_SIZE
+        {
+            return Err(ApiError::invalid_request(format!(
+                "rules.hand_size must be at most {}, got {}",
+                MAX_HAND_SIZE, rules.hand_size
+            )));
+        }
+        if let Some(discards_remaining) = self.discards_remaining
+            && discards_remaining > MAX_DISCARDS_REMAINING
+        {
+            return Err(ApiError::invalid_request(format!(
+                "discards_remaining must be at most {}, got {}",
+                MAX_DISCARDS_REMAINING, discards_remaining
+            )));
+        }
+        if let Some(PredictionBackend::MonteCarlo(config)) = &self.backend {
+            if config.max_samples > MAX_SAMPLES {
+                return Err(ApiError::invalid_request(format!(
+                    "max_samples must be at most {}, got {}",
+                    MAX_SAMPLES, config.max_samples
+                )));
+            }
+            if config.inner_samples > MAX_INNER_SAMPLES {
+                return Err(ApiError::invalid_request(format!(
+                    "inner_samples must be at most {}, got {}",
+                    MAX_INNER_SAMPLES, config.inner_samples
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects exact predictions that would score more than [`MAX_WORK`] hands, since a
+    /// blocking job keeps running after its request times out. Sampled predictions are
+    /// bounded by their sample counts, and plans sample themselves when they get too big.
+    fn check_work(&self, optimizing: bool) -> Result<(), ApiError> {
+        let planning = optimizing && self.discards_remaining.is_some_and(|discards| discards != 1);
+        if planning || matches!(self.backend, Some(PredictionBackend::MonteCarlo(_))) {
+            return Ok(());
+        }
+        let rules = self.rules.clone().unwrap_or_default();
+        let deck_size = self.deck.as_ref().map_or(Deck::standard().len(), Deck::len);
+        let left = deck_size.saturating_sub(self.hand.len() + self.discarded_cards.len());
+        let work = if optimizing {
+            exact_work(self.hand.len(), left, 1, &rules)
+        } else {
+            exact_work(self.hand.len().saturating_sub(self.discard.len()), left, 0, &rules)
+        };
+        if work > MAX_WORK as f64 {
+            return Err(ApiError::invalid_request(format!(
+                "The prediction would score about {:.0} hands, more than the limit of {}; use the MonteCarlo backend",
+                work, MAX_WORK
+            )));
+        }
+        Ok(())
+    }
+
+    fn distribution(&self) -> anyhow::Result<OutcomeDistribution> {
+        self.backend
+            .clone()
+            .unwrap_or_default()
+            .predictor(self.hand.clone(), self.deck.clone().unwrap_or_default(), self.rules.clone().unwrap_or_default())
+            .calculate_outcome_distribution(
+                PredictRequest::optional(&self.discarded_cards),
+                PredictRequest::optional(&self.discard),
+            )
+    }
+
+    fn discard_options(&self) -> anyhow::Result<Vec<DiscardOption>> {
+        let mut predictor = self.backend.clone().unwrap_or_default().predictor(
+            self.hand.clone(),
+            self.deck.clone().unwrap_or_default(),
+            self.rules.clone().unwrap_or_default(),
+        );
+        let discarded_cards = PredictRequest::optional(&self.discarded_cards);
+        match self.discards_remaining {
+            Some(discards_remaining) if discards_remaining != 1 => {
+                predictor.plan_discards(discarded_cards, discards_remaining, self.joker)
+            }
+            _ => predictor.optimize_discard(discarded_cards, self.joker),
+        }
+    }
+}
+
+/// Every route of the API.
+pub fn router() -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/openapi.json", get(openapi))
+        .route("/probability/{rank}", post(probability))
+        .route("/distribution", post(distribution))
+        .route("/optimize-discard", post(optimize_discard))
+}
+
+/// Serves the API on `address` until the process is stopped. Port 0 picks a free port,
+/// which is printed to stderr along with the rest of the address.
+pub async fn serve(address: SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router()).await?;
+    Ok(())
+}
+
+/// Runs `work` on the blocking pool, since exact predictions can take seconds, and gives
+/// up waiting for it after [`TIMEOUT`].
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Result<Json<T>, ApiError> {
+    match tokio::time::timeout(TIMEOUT, tokio::task::spawn_blocking(work)).await {
+        Ok(Ok(result)) => Ok(Json(result?)),
+        Err(_) => Err(ApiError {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            code: "timeout",
+            message: format!("The prediction took longer than {} seconds", TIMEOUT.as_secs()),
+            cards: Vec::new(),
+        }),
+        Ok(Err(error)) => Err(ApiError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            code: "internal",
+            message: error.to_string(),
+            cards: Vec::new(),
+        }),
+    }
+}
+
+async fn health() -> Json<Health> {
+    Json(Health {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI)
+}
+
+async fn probability(
+    rank: Result<Path<HandRank>, PathRejection>,
+    request: Result<Json<PredictRequest>, JsonRejection>,
+) -> Result<Json<Probability>, ApiError> {
+    let Path(rank) = rank?;
+    let Json(request) = request?;
+    request.check_limits()?;
+    request.check_work(false)?;
+    blocking(move || {
+        Ok(Probability {
+            rank,
+            probability: request.distribution()?.probability(rank),
+        })
+    })
+    .await
+}
+
+async fn distribution(
+    request: Result<Json<PredictRequest>, JsonRejection>,
+) -> Result<Json<OutcomeDistribution>, ApiError> {
+    let Json(request) = request?;
+    request.check_limits()?;
+    request.check_work(false)?;
+    blocking(move || request.distribution()).await
+}
+
+async fn optimize_discard(
+    request: Result<Json<PredictRequest>, JsonRejection>,
+) -> Result<Json<Vec<DiscardOption>>, ApiError> {
+    let Json(request) = request?;
+    request.check_limits()?;
+    request.check_work(true)?;
+    blocking(move || request.discard_options()).await
+}
@@ -0,0 +1,116 @@
+mod test {
+    use serde_json::Value;
+    use std::process::{Command, Output};
+
+    fn balatro_cli(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_balatro-cli")).args(args).output().unwrap()
+    }
+
+    /// Runs a command that should succeed and returns what it printed.
+    fn run(args: &[&str]) -> String {
+        let output = balatro_cli(args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn run_json(args: &[&str]) -> Value {
+        let mut args = args.to_vec();
+        args.push("--json");
+        serde_json::from_str(&run(&args)).unwrap()
+    }
+
+    #[test]
+    fn test_predict() {
+        let args = ["predict", "2h 7h 9h Kh Ks", "--discard", "Ks"];
+        let text = run(&args);
+        assert!(text.starts_with("Expected score: "));
+
+        let expected_score = run_json(&args).as_f64().unwrap();
+        assert!(text.contains(&format!("{:.1}", expected_score)));
+    }
+
+    #[test]
+    fn test_distribution() {
+        // Four hearts, drawing one card for the flush
+        let args = ["distribution", "2h", "7h", "9h", "Kh"];
+        let text = run(&args);
+        assert_eq!(text.lines().count(), 10);
+        assert!(text.lines().any(|line| line.starts_with("Flush") && line.ends_with("18.75%")));
+
+        let distribution = run_json(&args);
+        assert_eq!(distribution["draws"], 48);
+        assert_eq!(distribution["probabilities"]["Flush"].as_f64().unwrap(), 9.0 / 48.0);
+    }
+
+    #[test]
+    fn test_optimize_discard() {
+        let args = ["optimize-discard", "2h 7h 9h Kh Ks", "--top", "3"];
+        let text = run(&args);
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.lines().next().unwrap().ends_with("discard 2h 7h 9h"));
+
+        let options = run_json(&args);
+        let scores: Vec<f64> = options.as_array().unwrap().iter().map(|option| option["expected_score"].as_f64().unwrap()).collect();
+        assert_eq!(scores.len(), 3);
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(options[0]["kept"].as_array().unwrap().len(), 2);
+
+        // With no discards left, standing is the only option
+        let text = run(&["optimize-discard", "2h 7h 9h Kh Ks", "--discards-remaining", "0"]);
+        assert_eq!(text.lines().count(), 1);
+        assert!(text.ends_with("discard nothing\n"));
+    }
+
+    #[test]
+    fn test_simulate() {
+        let args = ["simulate", "2h 7h 9h Kh Ks", "--discard", "Ks", "--seed", "7", "--samples", "2000"];
+        let text = run(&args);
+        assert!(text.starts_with("Samples: 2000"));
+
+        let outcome = run_json(&args);
+        assert_eq!(outcome["samples"], 2000);
+        let flush = &outcome["probabilities"]["Flush"];
+        assert!(flush["lower"].as_f64().unwrap() <= 9.0 / 47.0 && 9.0 / 47.0 <= flush["upper"].as_f64().unwrap());
+        // The same seed draws the same cards
+        assert_eq!(run_json(&args), outcome);
+    }
+
+    #[test]
+    fn test_errors() {
+        let output = balatro_cli(&["distribution", "As", "As"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("error: "));
+
+        // Bad arguments are rejected by the parser before anything runs
+        assert!(!balatro_cli(&["predict", "Zz"]).status.success());
+        assert!(!balatro_cli(&["distribution", "Ah", "--rules", "poker"]).status.success());
+    }
+
+    #[cfg(feature = "serve")]
+    #[test]
+    fn test_serve() {
+        use std::io::{BufRead, BufReader, Read, Write};
+        use std::net::TcpStream;
+        use std::process::Stdio;
+
+        let mut server = Command::new(env!("CARGO_BIN_EXE_balatro-cli"))
+            .args(["serve", "--port", "0"])
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(server.stderr.take().unwrap()).read_line(&mut line).unwrap();
+        let address = line.trim().strip_prefix("Listening on http://").unwrap();
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        server.kill().unwrap();
+        server.wait().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        let body: Value = serde_json::from_str(response.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+        assert_eq!(body["status"], "ok");
+    }
+}
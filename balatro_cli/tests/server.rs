@@ -0,0 +1,119 @@
+#[cfg(feature = "serve")]
+mod test {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use balatro_cli::server::router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn call(method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(match body {
+                Some(body) => Body::from(body.to_string()),
+                None => Body::empty(),
+            })
+            .unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn card(face: &str, suit: &str) -> Value {
+        json!({ "card_face": face, "card_suit": suit })
+    }
+
+    #[tokio::test]
+    async fn test_health_and_openapi() {
+        let (status, health) = call("GET", "/health", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(health["status"], "ok");
+
+        let (status, openapi) = call("GET", "/openapi.json", None).await;
+        assert_eq!(status, StatusCode::OK);
+        for path in ["/health", "/probability/{rank}", "/distribution", "/optimize-discard"] {
+            assert!(openapi["paths"][path].is_object(), "{} is not documented", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_predictions() {
+        // Four hearts, drawing one card for the flush
+        let body = json!({
+            "hand": [card("Two", "Hearts"), card("Seven", "Hearts"), card("Nine", "Hearts"), card("King", "Hearts")],
+        });
+        let (status, probability) = call("POST", "/probability/Flush", Some(body.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        let flush = probability["probability"].as_f64().unwrap();
+        assert!((flush - 9.0 / 48.0).abs() < 1e-6);
+
+        let (status, distribution) = call("POST", "/distribution", Some(body.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(distribution["draws"], 48);
+        assert_eq!(distribution["probabilities"]["Flush"].as_f64().unwrap(), flush);
+
+        let body = json!({
+            "hand": [card("Two", "Hearts"), card("Seven", "Hearts"), card("Nine", "Hearts"), card("King", "Hearts"), card("King", "Spades")],
+            "discarded_cards": ["3c"],
+        });
+        let (status, options) = call("POST", "/optimize-discard", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(options.as_array().unwrap().len(), 32);
+
+        let body = json!({ "hand": ["2h", "7h", "9h", "Kh", "Ks"], "discards_remaining": 0 });
+        let (status, options) = call("POST", "/optimize-discard", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(options.as_array().unwrap().len(), 1);
+        assert_eq!(options[0]["discard"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_validation() {
+        let ace = card("Ace", "Spades");
+        let (status, error) = call("POST", "/distribution", Some(json!({ "hand": [ace, ace] }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error["code"], "duplicate_card");
+        assert_eq!(error["cards"], json!([ace]));
+
+        let (status, error) = call("POST", "/distribution", Some(json!({ "hand": [], "extra": 1 }))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error["code"], "invalid_request");
+
+        let (status, error) = call("POST", "/probability/Jackpot", Some(json!({ "hand": [] }))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error["code"], "invalid_request");
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        // Anything that would keep the server busy for too long is turned away up front
+        let hand = json!(["2h", "7h", "9h", "Kh", "Ks"]);
+        for body in [
+            json!({ "hand": hand, "discards_remaining": 4 }),
+            json!({ "hand": hand, "rules": { "hand_size": 12, "max_discard_size": 5 } }),
+            json!({ "hand": hand, "backend": { "type": "MonteCarlo", "max_samples": 1_000_000_000u64 } }),
+            json!({ "hand": hand, "backend": { "type": "MonteCarlo", "inner_samples": 100_000 } }),
+        ] {
+            let (status, error) = call("POST", "/optimize-discard", Some(body.clone())).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+            assert_eq!(error["code"], "invalid_request");
+        }
+
+        // Enumerating eight cards from a fresh deck passes every other check
+        let body = json!({ "hand": [], "rules": { "hand_size": 8 } });
+        for path in ["/distribution", "/probability/Flush", "/optimize-discard"] {
+            let (status, error) = call("POST", path, Some(body.clone())).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", path);
+            assert_eq!(error["code"], "invalid_request");
+        }
+
+        // A full Balatro hand is still predicted exactly
+        let hand = json!(["2h", "7h", "9h", "Kh", "Ks", "3c", "5d", "8s"]);
+        let body = json!({ "hand": hand, "rules": { "hand_size": 8 } });
+        let (status, _) = call("POST", "/distribution", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
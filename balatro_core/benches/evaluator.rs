@@ -0,0 +1,51 @@
+use balatro_core::card_data::CardData;
+use balatro_core::card_set::CardSet;
+use balatro_core::deck::DECK;
+use balatro_core::evaluator::{evaluate_hand, evaluate_set};
+use balatro_core::lookup::lookup_five;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::hint::black_box;
+
+/// Hands evaluated per iteration, so throughput reads as hands per second.
+const HANDS: usize = 10_000;
+
+/// `HANDS` random hands of `size` cards, the same on every run.
+fn hands(size: usize) -> Vec<Vec<CardData>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut deck = DECK.to_vec();
+    (0..HANDS)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            deck[..size].to_vec()
+        })
+        .collect()
+}
+
+fn bench_evaluator(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evaluator");
+    group.throughput(Throughput::Elements(HANDS as u64));
+
+    for size in [5, 7] {
+        let hands = hands(size);
+        let sets: Vec<CardSet> = hands.iter().map(|hand| CardSet::from(hand.as_slice())).collect();
+
+        group.bench_function(format!("evaluate_set/{size}"), |b| {
+            b.iter(|| sets.iter().map(|&set| evaluate_set(black_box(set)).key).fold(0, u32::wrapping_add))
+        });
+        group.bench_function(format!("evaluate_hand/{size}"), |b| {
+            b.iter(|| hands.iter().map(|hand| evaluate_hand(black_box(hand)).key).fold(0, u32::wrapping_add))
+        });
+        if size == 5 {
+            group.bench_function("lookup_five/5", |b| {
+                b.iter(|| sets.iter().filter_map(|&set| lookup_five(black_box(set))).map(|entry| entry.score.total).sum::<u32>())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluator);
+criterion_main!(benches);
@@ -0,0 +1,166 @@
+use crate::card_data::{BalatroPredictor, CardData};
+use crate::deck::Deck;
+use crate::distribution::OutcomeDistribution;
+use crate::error::PredictorError;
+use crate::exact::enumerate_draws;
+use crate::joker::Joker;
+use crate::monte_carlo::{MonteCarloConfig, MonteCarloPredictor};
+use crate::optimizer::{self, DiscardOption};
+use crate::planner;
+use crate::rules::RuleSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which engine answers a prediction: exact enumeration or sampling.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum PredictionBackend {
+    #[default]
+    Exact,
+    MonteCarlo(MonteCarloConfig),
+}
+
+impl PredictionBackend {
+    pub fn predictor(&self, hand: Vec<CardData>, deck: Deck, rules: RuleSet) -> Box<dyn BalatroPredictor> {
+        match self {
+            PredictionBackend::Exact => Box::new(ExactPredictor::new(hand, deck, rules)),
+            PredictionBackend::MonteCarlo(config) => {
+                Box::new(MonteCarloPredictor::new(hand, config.clone(), deck, rules))
+            }
+        }
+    }
+}
+
+/// A [`BalatroPredictor`] that enumerates every draw from a deck under a rule set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExactPredictor {
+    pub hand: Vec<CardData>,
+    pub deck: Deck,
+    pub rules: RuleSet,
+}
+
+impl ExactPredictor {
+    pub fn new(hand: Vec<CardData>, deck: Deck, rules: RuleSet) -> Self {
+        ExactPredictor { hand, deck, rules }
+    }
+}
+
+/// Takes `discard` out of `hand`, failing with every discarded card that is not in the hand.
+pub(crate) fn kept_after_discard(
+    hand: &[CardData],
+    discard: &[CardData],
+) -> Result<Vec<CardData>, PredictorError> {
+    let mut kept = hand.to_vec();
+    let mut missing = Vec::new();
+    for card in discard {
+        match kept.iter().position(|kept_card| kept_card == card) {
+            Some(position) => {
+                kept.remove(position);
+            }
+            None => missing.push(*card),
+        };
+    }
+    if !missing.is_empty() {
+        return Err(PredictorError::NotInHand { cards: missing });
+    }
+    Ok(kept)
+}
+
+impl BalatroPredictor for ExactPredictor {
+    fn calculate_outcome_distribution(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+    ) -> anyhow::Result<OutcomeDistribution> {
+        let discard = discard.unwrap_or_default();
+        let kept = kept_after_discard(&self.hand, &discard)?;
+        let discarded_cards = discarded_cards.unwrap_or_default();
+        let outcome = enumerate_draws(&kept, &discard, &discarded_cards, &self.deck, None, &self.rules)?;
+        Ok(outcome.distribution())
+    }
+
+    fn calculate_expected_score(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<f32> {
+        let discard = discard.unwrap_or_default();
+        let kept = kept_after_discard(&self.hand, &discard)?;
+        let discarded_cards = discarded_cards.unwrap_or_default();
+        let outcome = enumerate_draws(&kept, &discard, &discarded_cards, &self.deck, joker, &self.rules)?;
+        Ok(outcome.expected_score() as f32)
+    }
+
+    fn optimize_discard(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        optimizer::optimize_discard(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            &self.deck,
+            joker,
+            &self.rules,
+        )
+    }
+
+    fn plan_discards(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discards_remaining: u8,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        planner::plan_discards(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            discards_remaining,
+            &self.deck,
+            joker,
+            &self.rules,
+        )
+    }
+}
+
+/// A bare hand predicts from the standard deck under the DST rules.
+impl BalatroPredictor for Vec<CardData> {
+    fn calculate_outcome_distribution(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+    ) -> anyhow::Result<OutcomeDistribution> {
+        dst(self).calculate_outcome_distribution(discarded_cards, discard)
+    }
+
+    fn calculate_expected_score(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<f32> {
+        dst(self).calculate_expected_score(discarded_cards, discard, joker)
+    }
+
+    fn optimize_discard(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        dst(self).optimize_discard(discarded_cards, joker)
+    }
+
+    fn plan_discards(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discards_remaining: u8,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        dst(self).plan_discards(discarded_cards, discards_remaining, joker)
+    }
+}
+
+fn dst(hand: &[CardData]) -> ExactPredictor {
+    ExactPredictor::new(hand.to_vec(), Deck::standard(), RuleSet::DST)
+}
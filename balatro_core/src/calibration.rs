@@ -0,0 +1,169 @@
+use crate::distribution::OutcomeDistribution;
+use crate::hand_rank::HandRank;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Number of equal width probability buckets in a reliability table.
+pub const BUCKETS: usize = 10;
+
+/// Smallest probability log loss is computed with, so an outcome predicted as impossible
+/// costs a lot instead of infinitely much.
+const MIN_PROBABILITY: f64 = 1e-15;
+
+/// A prediction of the finished hand next to the hand it actually finished as.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PredictionOutcome {
+    pub distribution: OutcomeDistribution,
+    pub actual: HandRank,
+}
+
+/// How well predicted probabilities matched what happened.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibrationReport {
+    /// Number of predictions the report was built from.
+    pub predictions: u64,
+    /// Mean squared error of the probabilities over every hand type, from 0 (perfect) to 2.
+    pub brier_score: f64,
+    /// Mean negative log of the probability given to the hand that happened.
+    pub log_loss: f64,
+    pub ranks: Vec<RankCalibration>,
+}
+
+/// Calibration of the probabilities given to one hand type.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RankCalibration {
+    pub rank: HandRank,
+    /// Average probability given to the hand type.
+    pub mean_predicted: f64,
+    /// How often the hand type actually happened.
+    pub observed: f64,
+    /// `mean_predicted - observed`: positive when the hand type is over-predicted.
+    pub bias: f64,
+    /// Gap between predicted and observed frequency averaged over the buckets, weighted by
+    /// how many predictions fell in each.
+    pub expected_calibration_error: f64,
+    pub buckets: Vec<ReliabilityBucket>,
+}
+
+/// The predictions that gave a hand type a probability between `lower` and `upper`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReliabilityBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+    pub mean_predicted: f64,
+    pub observed: f64,
+    /// `mean_predicted - observed`: positive when these predictions were overconfident in
+    /// the hand type, negative when they were underconfident.
+    pub bias: f64,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Tally {
+    count: u64,
+    predicted: f64,
+    hits: u64,
+}
+
+impl Tally {
+    fn add(&mut self, probability: f64, hit: bool) {
+        self.count += 1;
+        self.predicted += probability;
+        self.hits += hit as u64;
+    }
+
+    fn mean_predicted(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.predicted / self.count as f64 }
+    }
+
+    fn observed(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.hits as f64 / self.count as f64 }
+    }
+}
+
+impl CalibrationReport {
+    /// Scores every prediction in `log` against the hand that actually happened.
+    pub fn new(log: &[PredictionOutcome]) -> Self {
+        let mut brier_sum = 0.0;
+        let mut log_loss_sum = 0.0;
+        let mut totals = [Tally::default(); HandRank::COUNT];
+        let mut buckets = [[Tally::default(); BUCKETS]; HandRank::COUNT];
+        for entry in log {
+            for rank in HandRank::ALL {
+                let probability = entry.distribution.probability(rank).clamp(0.0, 1.0) as f64;
+                let hit = rank == entry.actual;
+                brier_sum += (probability - hit as u8 as f64).powi(2);
+                if hit {
+                    log_loss_sum -= probability.max(MIN_PROBABILITY).ln();
+                }
+
+                // Probabilities are kept as f32, so 0.9 may come back just under it. A
+                // probability of exactly 1 belongs in the top bucket.
+                let bucket = ((probability * BUCKETS as f64 + 1e-6) as usize).min(BUCKETS - 1);
+                totals[rank.index()].add(probability, hit);
+                buckets[rank.index()][bucket].add(probability, hit);
+            }
+        }
+
+        let count = log.len() as f64;
+        let ranks = HandRank::ALL
+            .iter()
+            .map(|&rank| {
+                let total = totals[rank.index()];
+                let buckets: Vec<ReliabilityBucket> = buckets[rank.index()]
+                    .iter()
+                    .enumerate()
+                    .map(|(index, tally)| ReliabilityBucket {
+                        lower: index as f64 / BUCKETS as f64,
+                        upper: (index + 1) as f64 / BUCKETS as f64,
+                        count: tally.count,
+                        mean_predicted: tally.mean_predicted(),
+                        observed: tally.observed(),
+                        bias: tally.mean_predicted() - tally.observed(),
+                    })
+                    .collect();
+                let expected_calibration_error = if log.is_empty() {
+                    0.0
+                } else {
+                    buckets.iter().map(|bucket| bucket.count as f64 * bucket.bias.abs()).sum::<f64>() / count
+                };
+                RankCalibration {
+                    rank,
+                    mean_predicted: total.mean_predicted(),
+                    observed: total.observed(),
+                    bias: total.mean_predicted() - total.observed(),
+                    expected_calibration_error,
+                    buckets,
+                }
+            })
+            .collect();
+
+        CalibrationReport {
+            predictions: log.len() as u64,
+            brier_score: if log.is_empty() { 0.0 } else { brier_sum / count },
+            log_loss: if log.is_empty() { 0.0 } else { log_loss_sum / count },
+            ranks,
+        }
+    }
+
+    /// The reliability table as CSV, one row per hand type and bucket.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,lower,upper,count,mean_predicted,observed,bias\n");
+        for rank in &self.ranks {
+            for bucket in &rank.buckets {
+                // Writing to a String cannot fail
+                let _ = writeln!(
+                    csv,
+                    "{:?},{:.1},{:.1},{},{:.6},{:.6},{:.6}",
+                    rank.rank, bucket.lower, bucket.upper, bucket.count, bucket.mean_predicted, bucket.observed, bucket.bias
+                );
+            }
+        }
+        csv
+    }
+}
@@ -0,0 +1,218 @@
+use crate::card_suits::CardSuit;
+use crate::distribution::OutcomeDistribution;
+use crate::optimizer::DiscardOption;
+use crate::faces::CardFace;
+use crate::hand_rank::HandRank;
+use crate::joker::Joker;
+use crate::modifiers::{Edition, Enhancement, Seal};
+#[cfg(feature = "serde")]
+use crate::notation::CardNotation;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+/// A playing card. Besides the struct form it deserializes from text notation such as
+/// `"Ah"`, see [`crate::notation`].
+///
+/// The Balatro modifiers are optional and left out of the JSON when a card has none.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "CardNotation"))]
+pub struct CardData {
+    pub card_suit: CardSuit,
+    pub card_face: CardFace,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub enhancement: Option<Enhancement>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub edition: Option<Edition>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub seal: Option<Seal>,
+}
+
+impl CardData {
+    /// A card without modifiers.
+    pub const fn new(card_face: CardFace, card_suit: CardSuit) -> Self {
+        CardData {
+            card_suit,
+            card_face,
+            enhancement: None,
+            edition: None,
+            seal: None,
+        }
+    }
+
+    /// The same suit and face without any modifiers.
+    pub const fn plain(&self) -> Self {
+        CardData::new(self.card_face, self.card_suit)
+    }
+
+    pub const fn is_plain(&self) -> bool {
+        self.enhancement.is_none() && self.edition.is_none() && self.seal.is_none()
+    }
+
+    /// Whether both cards have the same suit and face, whatever their modifiers.
+    pub fn same_card(&self, other: &CardData) -> bool {
+        self.card_suit == other.card_suit && self.card_face == other.card_face
+    }
+
+    /// Stone cards have no rank or suit.
+    pub fn is_stone(&self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+
+    /// Wild cards count as every suit.
+    pub fn is_wild(&self) -> bool {
+        self.enhancement == Some(Enhancement::Wild)
+    }
+
+    /// Whether the card counts as `suit`; a Wild card counts as every suit and a Stone card
+    /// as none.
+    pub fn is_suit(&self, suit: CardSuit) -> bool {
+        match self.enhancement {
+            Some(Enhancement::Wild) => true,
+            Some(Enhancement::Stone) => false,
+            _ => self.card_suit == suit,
+        }
+    }
+
+    /// Whether the card has the rank of `face`, which a Stone card never has.
+    pub fn is_face(&self, face: CardFace) -> bool {
+        !self.is_stone() && self.card_face == face
+    }
+
+    pub fn is_ace(&self) -> bool {
+        self.is_face(CardFace::Ace)
+    }
+    pub fn is_face_card(&self) -> bool {
+        self.is_face(CardFace::King)
+            || self.is_face(CardFace::Queen)
+            || self.is_face(CardFace::Jack)
+    }
+}
+
+/// Probability queries for a hand.
+///
+/// The hand holds the cards being kept. It is drawn back up to a full hand from whatever
+/// is left of the deck once the hand and `discarded_cards` have been taken out, and every
+/// probability is exact over all of those draws.
+pub trait BalatroPredictor {
+    fn calculate_high_card_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::HighCard)
+    }
+    fn calculate_pair_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::Pair)
+    }
+    fn calculate_two_pair_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::TwoPair)
+    }
+    fn calculate_three_of_a_kind_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::ThreeOfAKind)
+    }
+    fn calculate_straight_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::Straight)
+    }
+    fn calculate_flush_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::Flush)
+    }
+    fn calculate_full_house_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::FullHouse)
+    }
+    fn calculate_four_of_a_kind_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::FourOfAKind)
+    }
+    fn calculate_straight_flush_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::StraightFlush)
+    }
+    fn calculate_royal_flush_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+    ) -> anyhow::Result<f32> {
+        self.rank_probability(discarded_cards, HandRank::RoyalFlush)
+    }
+    /// Probability of finishing as `rank` without throwing anything away, read from
+    /// [`BalatroPredictor::calculate_outcome_distribution`].
+    fn rank_probability(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        rank: HandRank,
+    ) -> anyhow::Result<f32> {
+        Ok(self.calculate_outcome_distribution(discarded_cards, None)?.probability(rank))
+    }
+    /// Probability of every hand type after throwing away `discard` (which must come from
+    /// the hand) and drawing replacements.
+    fn calculate_outcome_distribution(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+    ) -> anyhow::Result<OutcomeDistribution>;
+    /// Expected chips × mult score of the finished hand after throwing away `discard`
+    /// (which must come from the hand) and drawing replacements, including what `joker` adds.
+    fn calculate_expected_score(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<f32>;
+    /// Every choice of cards to throw away from the hand, best expected score first.
+    fn optimize_discard(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>>;
+    /// Every first discard from the hand, best first, assuming the remaining discards are
+    /// played optimally afterwards.
+    fn plan_discards(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discards_remaining: u8,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>>;
+}
+
+impl Debug for CardData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CardData {{ card_suit: {:?}, card_face: {:?} ({})",
+            self.card_suit,
+            self.card_face,
+            self.card_face.value()
+        )?;
+        if let Some(enhancement) = self.enhancement {
+            write!(f, ", enhancement: {:?}", enhancement)?;
+        }
+        if let Some(edition) = self.edition {
+            write!(f, ", edition: {:?}", edition)?;
+        }
+        if let Some(seal) = self.seal {
+            write!(f, ", seal: {:?}", seal)?;
+        }
+        write!(f, " }}")
+    }
+}
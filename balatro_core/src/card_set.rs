@@ -0,0 +1,248 @@
+use crate::card_data::CardData;
+use crate::card_suits::CardSuit;
+use crate::faces::CardFace;
+use std::fmt::{Debug, Formatter};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign};
+
+/// Suits in index order; a card's index is `13 * suit + face`.
+pub const SUITS: [CardSuit; 4] = [CardSuit::Clubs, CardSuit::Diamonds, CardSuit::Hearts, CardSuit::Spades];
+
+/// Faces in index order, from Two up to Ace.
+pub const FACES: [CardFace; 13] = [
+    CardFace::Two,
+    CardFace::Three,
+    CardFace::Four,
+    CardFace::Five,
+    CardFace::Six,
+    CardFace::Seven,
+    CardFace::Eight,
+    CardFace::Nine,
+    CardFace::Ten,
+    CardFace::Jack,
+    CardFace::Queen,
+    CardFace::King,
+    CardFace::Ace,
+];
+
+/// Bits of a 13 bit rank mask, one per face in [`FACES`] order.
+pub const ALL_RANKS: u16 = (1 << 13) - 1;
+
+/// One bit for each face across all four suits, with Two in the lowest bit.
+const RANK_MASK: u64 = 1 | 1 << 13 | 1 << 26 | 1 << 39;
+
+/// A set of distinct cards stored as one bit per card of the 52 card deck.
+///
+/// Cards are indexed `13 * suit + face` with suits in [`SUITS`] order and faces in
+/// [`FACES`] order, so every suit is a contiguous block of 13 bits.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(pub u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const FULL: CardSet = CardSet((1 << 52) - 1);
+
+    /// Index of `card` in `0..52`.
+    pub const fn index(card: &CardData) -> u8 {
+        13 * card.card_suit as u8 + card.card_face as u8
+    }
+
+    /// The card at `index`, which must be below 52.
+    pub const fn card(index: u8) -> CardData {
+        CardData::new(FACES[(index % 13) as usize], SUITS[(index / 13) as usize])
+    }
+
+    pub const fn from_card(card: &CardData) -> Self {
+        CardSet(1 << CardSet::index(card))
+    }
+
+    /// Every card of `face`, one per suit.
+    pub const fn rank(face: CardFace) -> Self {
+        CardSet(RANK_MASK << face as u8)
+    }
+
+    /// Every card of `suit`.
+    pub const fn suit(suit: CardSuit) -> Self {
+        CardSet((ALL_RANKS as u64) << (13 * suit as u8))
+    }
+
+    pub const fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, card: &CardData) -> bool {
+        self.0 & CardSet::from_card(card).0 != 0
+    }
+
+    /// Adds `card`, returning whether it was not already in the set.
+    pub fn insert(&mut self, card: &CardData) -> bool {
+        let inserted = !self.contains(card);
+        self.0 |= CardSet::from_card(card).0;
+        inserted
+    }
+
+    /// Removes `card`, returning whether it was in the set.
+    pub fn remove(&mut self, card: &CardData) -> bool {
+        let removed = self.contains(card);
+        self.0 &= !CardSet::from_card(card).0;
+        removed
+    }
+
+    pub const fn union(self, other: CardSet) -> Self {
+        CardSet(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other: CardSet) -> Self {
+        CardSet(self.0 & other.0)
+    }
+
+    pub const fn difference(self, other: CardSet) -> Self {
+        CardSet(self.0 & !other.0)
+    }
+
+    /// Every card of the deck that is not in the set.
+    pub const fn complement(self) -> Self {
+        CardSet(!self.0 & CardSet::FULL.0)
+    }
+
+    pub const fn is_subset(&self, other: CardSet) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Faces held in `suit`, as a 13 bit rank mask.
+    pub const fn suit_ranks(&self, suit: CardSuit) -> u16 {
+        ((self.0 >> (13 * suit as u8)) as u16) & ALL_RANKS
+    }
+
+    /// Faces held in any suit, as a 13 bit rank mask.
+    pub const fn ranks(&self) -> u16 {
+        let bits = self.0 | self.0 >> 13 | self.0 >> 26 | self.0 >> 39;
+        bits as u16 & ALL_RANKS
+    }
+
+    /// Number of cards of `face` in the set.
+    pub const fn count(&self, face: CardFace) -> u32 {
+        self.intersection(CardSet::rank(face)).len()
+    }
+
+    /// Cards in index order.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+/// Iterator over the cards of a [`CardSet`] in index order.
+#[derive(Clone, Debug)]
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = CardData;
+
+    fn next(&mut self) -> Option<CardData> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(CardSet::card(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = CardData;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl<'a> FromIterator<&'a CardData> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a CardData>>(iter: I) -> Self {
+        iter.into_iter().fold(CardSet::EMPTY, |set, card| set | CardSet::from_card(card))
+    }
+}
+
+impl FromIterator<CardData> for CardSet {
+    fn from_iter<I: IntoIterator<Item = CardData>>(iter: I) -> Self {
+        iter.into_iter().fold(CardSet::EMPTY, |set, card| set | CardSet::from_card(&card))
+    }
+}
+
+impl From<&[CardData]> for CardSet {
+    fn from(cards: &[CardData]) -> Self {
+        cards.iter().collect()
+    }
+}
+
+impl From<CardData> for CardSet {
+    fn from(card: CardData) -> Self {
+        CardSet::from_card(&card)
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, other: CardSet) {
+        self.0 |= other.0;
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, other: CardSet) {
+        self.0 &= other.0;
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, other: CardSet) {
+        self.0 &= !other.0;
+    }
+}
+
+impl Not for CardSet {
+    type Output = CardSet;
+
+    fn not(self) -> CardSet {
+        self.complement()
+    }
+}
+
+impl Debug for CardSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CardSuit {
+    #[default]
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl CardSuit {
+    pub const fn from_index(index: u8) -> Self {
+        match index {
+            0 => CardSuit::Hearts,
+            1 => CardSuit::Diamonds,
+            2 => CardSuit::Clubs,
+            3 => CardSuit::Spades,
+            _ => panic!("invalid suit"),
+        }
+    }
+}
+
@@ -0,0 +1,312 @@
+use crate::card_data::CardData;
+use crate::card_set::CardSet;
+use crate::card_suits::CardSuit;
+use crate::error::PredictorError;
+use crate::exact::for_each_combination;
+use crate::faces::CardFace;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
+
+pub const DECK: [CardData; 52] = get_deck_ace_high();
+pub const fn get_deck_ace_high() -> [CardData; 52] {
+    let mut deck = [CardData::new(CardFace::Ace, CardSuit::Clubs); 52];
+    let mut i = 0;
+    let mut suit_index = 0;
+    while suit_index < 4 {
+        let suit = CardSuit::from_index(suit_index);
+        let mut rank = 2;
+        while rank <= 14 {
+            deck[i] = CardData::new(CardFace::from_rank(rank).expect("invalid card rank"), suit);
+            i += 1;
+            rank += 1;
+        }
+        suit_index += 1;
+    }
+    deck
+}
+
+/// The cards a round is drawn from, which may be missing cards, hold several copies of one
+/// or hold cards with modifiers. It serializes as the list of its cards, one entry per copy.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<CardData>", into = "Vec<CardData>"))]
+pub struct Deck {
+    /// Copies of every card without modifiers, indexed by [`CardSet::index`].
+    counts: [u32; 52],
+    /// Cards with modifiers, one entry per copy.
+    modified: Vec<CardData>,
+}
+
+/// A change to the standard deck, such as a card destroyed or duplicated during a run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum DeckChange {
+    /// Takes every card of a face out of the deck.
+    RemoveFace { face: CardFace },
+    /// Takes every card of a suit out of the deck.
+    RemoveSuit { suit: CardSuit },
+    /// Takes every copy of a card out of the deck.
+    RemoveCard { card: CardData },
+    /// Adds copies of a card on top of those already in the deck.
+    AddCard { card: CardData, copies: u32 },
+}
+
+impl Deck {
+    /// One of each of the 52 cards.
+    pub fn standard() -> Self {
+        DECK.into_iter().collect()
+    }
+
+    pub fn empty() -> Self {
+        Deck {
+            counts: [0; 52],
+            modified: Vec::new(),
+        }
+    }
+
+    /// The standard deck with `changes` applied in order.
+    pub fn from_changes(changes: &[DeckChange]) -> Self {
+        let mut deck = Deck::standard();
+        for change in changes {
+            deck.apply(change);
+        }
+        deck
+    }
+
+    pub fn apply(&mut self, change: &DeckChange) {
+        match change {
+            DeckChange::RemoveFace { face } => self.retain(|card| card.card_face != *face),
+            DeckChange::RemoveSuit { suit } => self.retain(|card| card.card_suit != *suit),
+            DeckChange::RemoveCard { card: removed } => self.retain(|card| card != removed),
+            DeckChange::AddCard { card, copies } => self.add(card, *copies),
+        }
+    }
+
+    /// Copies of `card` in the deck, modifiers included.
+    pub fn count(&self, card: &CardData) -> u32 {
+        if card.is_plain() {
+            self.counts[CardSet::index(card) as usize]
+        } else {
+            self.modified.iter().filter(|copy| *copy == card).count() as u32
+        }
+    }
+
+    /// Copies of `card` in the deck with the same suit and face, whatever their modifiers.
+    pub fn copies(&self, card: &CardData) -> u32 {
+        self.counts[CardSet::index(card) as usize]
+            + self.modified.iter().filter(|copy| copy.same_card(card)).count() as u32
+    }
+
+    /// Number of cards in the deck, counting every copy.
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum::<usize>() + self.modified.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether every card is a single copy without modifiers, so every hand drawn from the
+    /// deck fits a [`CardSet`].
+    pub fn fits_card_set(&self) -> bool {
+        self.modified.is_empty() && self.counts.iter().all(|&count| count <= 1)
+    }
+
+    pub fn add(&mut self, card: &CardData, copies: u32) {
+        if card.is_plain() {
+            self.counts[CardSet::index(card) as usize] += copies;
+        } else {
+            self.modified.extend(std::iter::repeat_n(*card, copies as usize));
+        }
+    }
+
+    /// Takes one copy of `card` out of the deck, returning whether there was one.
+    ///
+    /// When the deck has no copy with the same modifiers, a copy of the same suit and face
+    /// is taken instead, as the card may have been enhanced since the deck was built.
+    pub fn remove(&mut self, card: &CardData) -> bool {
+        let index = CardSet::index(card) as usize;
+        if card.is_plain() && self.counts[index] > 0 {
+            self.counts[index] -= 1;
+            return true;
+        }
+        if let Some(position) = self.modified.iter().position(|copy| copy == card) {
+            self.modified.remove(position);
+            return true;
+        }
+        if self.counts[index] > 0 {
+            self.counts[index] -= 1;
+            return true;
+        }
+        match self.modified.iter().position(|copy| copy.same_card(card)) {
+            Some(position) => {
+                self.modified.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Keeps only the cards `keep` returns true for, with all of their copies.
+    pub fn retain(&mut self, mut keep: impl FnMut(&CardData) -> bool) {
+        for index in 0..52 {
+            if !keep(&CardSet::card(index)) {
+                self.counts[index as usize] = 0;
+            }
+        }
+        self.modified.retain(|card| keep(card));
+    }
+
+    /// What is left to draw once `cards` have been taken out, one copy per entry.
+    ///
+    /// Fails with every one of `cards` that has no copy left in the deck.
+    pub fn without(&self, cards: &[CardData]) -> Result<Deck, PredictorError> {
+        let mut deck = self.clone();
+        let missing: Vec<CardData> = cards.iter().filter(|card| !deck.remove(card)).copied().collect();
+        if !missing.is_empty() {
+            return Err(PredictorError::MissingFromDeck { cards: missing });
+        }
+        Ok(deck)
+    }
+
+    /// Like [`Deck::without`], for cards already known to come from the deck.
+    pub(crate) fn remaining(&self, cards: &[CardData]) -> Deck {
+        let mut deck = self.clone();
+        for card in cards {
+            deck.remove(card);
+        }
+        deck
+    }
+
+    /// Every card, repeated once per copy: the cards without modifiers in index order, then
+    /// the others.
+    pub fn cards(&self) -> Vec<CardData> {
+        (0..52u8)
+            .flat_map(|index| std::iter::repeat_n(CardSet::card(index), self.counts[index as usize] as usize))
+            .chain(self.modified.iter().copied())
+            .collect()
+    }
+
+    /// Calls `visit` once for every distinct set of `count` cards that can be drawn, along
+    /// with the number of ways of drawing it when the deck holds several copies of a card.
+    ///
+    /// The weights add up to the number of ways of drawing `count` of the deck's cards.
+    pub fn for_each_draw(&self, count: usize, mut visit: impl FnMut(&[CardData], u64)) {
+        if self.fits_card_set() {
+            for_each_combination(&self.cards(), count, |drawn| visit(drawn, 1));
+            return;
+        }
+
+        let mut cards: Vec<(CardData, u32)> = (0..52u8)
+            .filter(|&index| self.counts[index as usize] > 0)
+            .map(|index| (CardSet::card(index), self.counts[index as usize]))
+            .collect();
+        for card in &self.modified {
+            match cards.iter_mut().find(|(distinct, _)| distinct == card) {
+                Some((_, copies)) => *copies += 1,
+                None => cards.push((*card, 1)),
+            }
+        }
+        let mut drawn = Vec::with_capacity(count);
+        draw_copies(&cards, count, 1, &mut drawn, &mut visit);
+    }
+}
+
+/// Chooses how many copies of each of `cards` to draw so `left` more are drawn, weighting
+/// every choice by the number of ways of picking those copies.
+fn draw_copies(
+    cards: &[(CardData, u32)],
+    left: usize,
+    weight: u64,
+    drawn: &mut Vec<CardData>,
+    visit: &mut impl FnMut(&[CardData], u64),
+) {
+    if left == 0 {
+        visit(drawn, weight);
+        return;
+    }
+    let Some((&(card, copies), rest)) = cards.split_first() else {
+        return;
+    };
+
+    let len = drawn.len();
+    for taken in 0..=left.min(copies as usize) {
+        drawn.resize(len + taken, card);
+        draw_copies(rest, left - taken, weight * binomial(copies as u64, taken as u64), drawn, visit);
+    }
+    drawn.truncate(len);
+}
+
+/// Number of ways of choosing `k` of `n` items.
+fn binomial(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |ways, i| ways * (n - i) / (i + 1))
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::standard()
+    }
+}
+
+impl Debug for Deck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.cards()).finish()
+    }
+}
+
+impl FromIterator<CardData> for Deck {
+    fn from_iter<I: IntoIterator<Item = CardData>>(iter: I) -> Self {
+        let mut deck = Deck::empty();
+        for card in iter {
+            deck.add(&card, 1);
+        }
+        deck
+    }
+}
+
+impl From<Vec<CardData>> for Deck {
+    fn from(cards: Vec<CardData>) -> Self {
+        cards.into_iter().collect()
+    }
+}
+
+impl From<Deck> for Vec<CardData> {
+    fn from(deck: Deck) -> Self {
+        deck.cards()
+    }
+}
+
+/// Reads a change such as `remove 2`, `remove h`, `remove Ah` or `add 3 As`.
+impl FromStr for DeckChange {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        match words.as_slice() {
+            ["remove", target] => {
+                if let Ok(card) = target.parse() {
+                    Ok(DeckChange::RemoveCard { card })
+                } else if let Ok(face) = target.parse() {
+                    Ok(DeckChange::RemoveFace { face })
+                } else {
+                    Ok(DeckChange::RemoveSuit { suit: target.parse()? })
+                }
+            }
+            ["add", card] => Ok(DeckChange::AddCard {
+                card: card.parse()?,
+                copies: 1,
+            }),
+            ["add", copies, card] => Ok(DeckChange::AddCard {
+                card: card.parse()?,
+                copies: copies.parse()?,
+            }),
+            _ => Err(anyhow::anyhow!(
+                "Cannot read deck change {:?}, expected \"remove <face, suit or card>\" or \"add [copies] <card>\"",
+                text
+            )),
+        }
+    }
+}
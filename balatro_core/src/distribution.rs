@@ -0,0 +1,31 @@
+use crate::hand_rank::HandRank;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Probability of finishing with each hand type. Every hand finishes as exactly one
+/// type, so the probabilities add up to 1.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OutcomeDistribution {
+    pub probabilities: BTreeMap<HandRank, f32>,
+    /// Number of draws the distribution was built from.
+    pub draws: u64,
+}
+
+impl OutcomeDistribution {
+    /// Builds a distribution from probabilities indexed by [`HandRank::index`].
+    pub fn from_probabilities(probabilities: [f64; HandRank::COUNT], draws: u64) -> Self {
+        OutcomeDistribution {
+            probabilities: HandRank::ALL
+                .iter()
+                .map(|&rank| (rank, probabilities[rank.index()] as f32))
+                .collect(),
+            draws,
+        }
+    }
+
+    pub fn probability(&self, rank: HandRank) -> f32 {
+        self.probabilities.get(&rank).copied().unwrap_or(0.0)
+    }
+}
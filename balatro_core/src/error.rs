@@ -0,0 +1,194 @@
+use crate::card_data::CardData;
+use crate::deck::Deck;
+use crate::rules::RuleSet;
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// Why a prediction cannot be made from the cards it was given.
+///
+/// It serializes as `{ code, message, cards }`, where `cards` lists the offending cards and
+/// is empty when the error is not about particular cards.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PredictorError {
+    /// The hand holds more cards than the rules allow.
+    HandSize { expected: usize, actual: usize },
+    /// The hand holds a card more often than the deck has copies of it.
+    DuplicateCard { cards: Vec<CardData> },
+    /// Cards in the hand that were also discarded earlier in the round.
+    DiscardedInHand { cards: Vec<CardData> },
+    /// Cards with no copy left in the deck.
+    MissingFromDeck { cards: Vec<CardData> },
+    /// Cards to discard that are not in the hand.
+    NotInHand { cards: Vec<CardData> },
+    /// A discard throws away more cards than the rules allow at once.
+    DiscardSize { max: usize, actual: usize },
+    /// More discards are planned than the rules allow in a round.
+    DiscardCount { max: u8, actual: u8 },
+    /// The deck runs out before the hand is full again.
+    NotEnoughCards { needed: usize, left: usize },
+    /// A discard was drawn for with a different number of cards than it takes to fill the hand.
+    DrawCount { expected: usize, actual: usize },
+    /// A round command was called before a round was started.
+    NoRound,
+    /// A rule set that cannot be played, with what is wrong with it.
+    InvalidRules(String),
+    /// Any other failure, kept as its message.
+    Other(String),
+}
+
+impl PredictorError {
+    /// Stable name of the variant for the frontend to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PredictorError::HandSize { .. } => "hand_size",
+            PredictorError::DuplicateCard { .. } => "duplicate_card",
+            PredictorError::DiscardedInHand { .. } => "discarded_in_hand",
+            PredictorError::MissingFromDeck { .. } => "missing_from_deck",
+            PredictorError::NotInHand { .. } => "not_in_hand",
+            PredictorError::DiscardSize { .. } => "discard_size",
+            PredictorError::DiscardCount { .. } => "discard_count",
+            PredictorError::NotEnoughCards { .. } => "not_enough_cards",
+            PredictorError::DrawCount { .. } => "draw_count",
+            PredictorError::NoRound => "no_round",
+            PredictorError::InvalidRules(_) => "invalid_rules",
+            PredictorError::Other(_) => "other",
+        }
+    }
+
+    /// The cards that caused the error, if it is about particular cards.
+    pub fn cards(&self) -> &[CardData] {
+        match self {
+            PredictorError::DuplicateCard { cards }
+            | PredictorError::DiscardedInHand { cards }
+            | PredictorError::MissingFromDeck { cards }
+            | PredictorError::NotInHand { cards } => cards,
+            _ => &[],
+        }
+    }
+}
+
+impl Display for PredictorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PredictorError::HandSize { expected, actual } => write!(
+                f,
+                "Too many cards in hand, expected at most {} but got {}",
+                expected, actual
+            ),
+            PredictorError::DuplicateCard { cards } => {
+                write!(f, "The hand holds {} more often than the deck does", list(cards))
+            }
+            PredictorError::DiscardedInHand { cards } => {
+                write!(f, "Cannot hold {}, it was already discarded", list(cards))
+            }
+            PredictorError::MissingFromDeck { cards } => {
+                write!(f, "Cannot take {} from the deck, it has no copy left", list(cards))
+            }
+            PredictorError::NotInHand { cards } => {
+                write!(f, "Cannot discard {}, it is not in the hand", list(cards))
+            }
+            PredictorError::DiscardSize { actual: 0, .. } => f.write_str("A discard must throw away at least one card"),
+            PredictorError::DiscardSize { max, actual } => write!(
+                f,
+                "Cannot discard {} cards, at most {} can be discarded at once",
+                actual, max
+            ),
+            PredictorError::DiscardCount { max, actual } => write!(
+                f,
+                "Cannot plan {} discards, the rules allow {} per round",
+                actual, max
+            ),
+            PredictorError::NotEnoughCards { needed, left } => write!(
+                f,
+                "Not enough cards left in the deck to draw {} cards, only {} are left",
+                needed, left
+            ),
+            PredictorError::DrawCount { expected, actual } => write!(
+                f,
+                "Drew {} cards, but {} are needed to fill the hand again",
+                actual, expected
+            ),
+            PredictorError::NoRound => f.write_str("No round is in progress, start one first"),
+            PredictorError::InvalidRules(message) => write!(f, "Invalid rules: {}", message),
+            PredictorError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for PredictorError {}
+
+/// Keeps a [`PredictorError`] raised inside an engine, and the message of anything else.
+impl From<anyhow::Error> for PredictorError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<PredictorError>() {
+            Ok(error) => error,
+            Err(error) => PredictorError::Other(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for PredictorError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PredictorError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("cards", self.cards())?;
+        state.end()
+    }
+}
+
+fn list(cards: &[CardData]) -> String {
+    cards.iter().map(|card| card.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Checks that `hand` and `discarded_cards` can both have been dealt from `deck` under `rules`.
+///
+/// A card may only repeat as often as the deck holds copies of it, so a hand with two Aces
+/// of Spades is fine for a deck with two of them but not for the standard deck.
+pub fn check_hand(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    deck: &Deck,
+    rules: &RuleSet,
+) -> Result<(), PredictorError> {
+    if hand.len() > rules.hand_size {
+        return Err(PredictorError::HandSize {
+            expected: rules.hand_size,
+            actual: hand.len(),
+        });
+    }
+
+    let held = |cards: &[CardData], card: &CardData| {
+        cards.iter().filter(|other| other.same_card(card)).count() as u32
+    };
+    let mut duplicates: Vec<CardData> = Vec::new();
+    let mut discarded: Vec<CardData> = Vec::new();
+    for card in hand {
+        if duplicates.iter().chain(&discarded).any(|seen| seen.same_card(card)) {
+            continue;
+        }
+        let copies = deck.copies(card);
+        let in_hand = held(hand, card);
+        let in_discards = held(discarded_cards, card);
+        if in_hand > 1 && in_hand > copies {
+            duplicates.push(*card);
+        } else if in_discards > 0 && in_hand + in_discards > copies {
+            discarded.push(*card);
+        }
+    }
+    if !duplicates.is_empty() {
+        return Err(PredictorError::DuplicateCard { cards: duplicates });
+    }
+    if !discarded.is_empty() {
+        return Err(PredictorError::DiscardedInHand { cards: discarded });
+    }
+
+    let mut dealt = hand.to_vec();
+    dealt.extend_from_slice(discarded_cards);
+    deck.without(&dealt)?;
+    Ok(())
+}
@@ -0,0 +1,350 @@
+use crate::card_data::CardData;
+use crate::card_set::{CardSet, FACES, SUITS};
+use crate::card_suits::CardSuit;
+use crate::faces::CardFace;
+use crate::hand_rank::HandRank;
+use crate::rules::RuleSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+
+/// The best hand that can be made from a set of cards.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HandEvaluation {
+    pub rank: HandRank,
+    /// Cards that make up the hand, strongest group first.
+    pub scoring_cards: Vec<CardData>,
+    /// Cards that are not part of the hand, highest first.
+    pub kickers: Vec<CardData>,
+    /// Tie-breaking key; a higher key beats a lower one and equal keys tie.
+    pub key: u32,
+}
+
+impl HandEvaluation {
+    /// Compares the strength of two hands by their keys: `Greater` when this hand beats
+    /// `other` and `Equal` when they tie, even if they are made of different cards.
+    pub fn compare_strength(&self, other: &HandEvaluation) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// The best hand in a [`CardSet`], worked out without allocating so the engines can
+/// classify millions of draws.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SetEvaluation {
+    pub rank: HandRank,
+    /// Cards that make up the hand.
+    pub scoring: CardSet,
+    /// Same tie-breaking key as [`HandEvaluation::key`].
+    pub key: u32,
+}
+
+/// Classifies `cards` as the best hand they contain, following the table in `game_rules.md`.
+pub fn evaluate_set(cards: CardSet) -> SetEvaluation {
+    evaluate_set_with(cards, &RuleSet::DST)
+}
+
+/// Classifies `cards` as the best hand they contain under `rules`.
+pub fn evaluate_set_with(cards: CardSet, rules: &RuleSet) -> SetEvaluation {
+    let by_suit = SUITS.map(|suit| cards.suit_ranks(suit));
+    let [clubs, diamonds, hearts, spades] = by_suit;
+    let pairs = clubs & diamonds | clubs & hearts | clubs & spades | diamonds & hearts | diamonds & spades | hearts & spades;
+    let trips = clubs & diamonds & hearts | clubs & diamonds & spades | clubs & hearts & spades | diamonds & hearts & spades;
+    let quads = clubs & diamonds & hearts & spades;
+
+    let flush_suit = (0..SUITS.len()).find(|&suit| by_suit[suit].count_ones() >= 5);
+
+    if let Some(suit) = flush_suit
+        && let Some(top) = straight_top(by_suit[suit], rules.ace_low)
+    {
+        let rank = if top == CardFace::Ace {
+            HandRank::RoyalFlush
+        } else {
+            HandRank::StraightFlush
+        };
+        let scoring = pick(CardSet::suit(SUITS[suit]), straight_ranks(top), 1);
+        return build_set(rank, cards, scoring);
+    }
+
+    if quads != 0 {
+        return build_set(HandRank::FourOfAKind, cards, pick(cards, highest(quads), 4));
+    }
+
+    if trips != 0 && pairs & !highest(trips) != 0 {
+        let three = highest(trips);
+        let scoring = pick(cards, three, 3) | pick(cards, highest(pairs & !three), 2);
+        return build_set(HandRank::FullHouse, cards, scoring);
+    }
+
+    if let Some(suit) = flush_suit {
+        let flush = top_ranks(by_suit[suit], 5);
+        return build_set(HandRank::Flush, cards, pick(CardSet::suit(SUITS[suit]), flush, 1));
+    }
+
+    if let Some(top) = straight_top(cards.ranks(), rules.ace_low) {
+        return build_set(HandRank::Straight, cards, pick(cards, straight_ranks(top), 1));
+    }
+
+    if trips != 0 {
+        return build_set(HandRank::ThreeOfAKind, cards, pick(cards, highest(trips), 3));
+    }
+
+    if pairs.count_ones() >= 2 {
+        return build_set(HandRank::TwoPair, cards, pick(cards, top_ranks(pairs, 2), 2));
+    }
+
+    if pairs != 0 {
+        return build_set(HandRank::Pair, cards, pick(cards, highest(pairs), 2));
+    }
+
+    build_set(HandRank::HighCard, cards, pick(cards, highest(cards.ranks()), 1))
+}
+
+/// Classifies `cards` as the best hand they contain, following the table in `game_rules.md`.
+pub fn evaluate_hand(cards: &[CardData]) -> HandEvaluation {
+    evaluate_hand_with(cards, &RuleSet::DST)
+}
+
+/// Classifies `cards` as the best hand they contain under `rules`.
+///
+/// `cards` may hold several copies of a card when they come from a [`crate::deck::Deck`]
+/// with duplicates, and Wild and Stone cards are ranked as their enhancement says. The
+/// scoring cards and kickers keep every card's modifiers.
+pub fn evaluate_hand_with(cards: &[CardData], rules: &RuleSet) -> HandEvaluation {
+    let set = CardSet::from(cards);
+    let special = cards.iter().any(|card| card.is_stone() || card.is_wild());
+    if special || set.len() as usize != cards.len() {
+        return evaluate_list(cards, rules);
+    }
+    let evaluation = evaluate_set_with(set, rules);
+
+    // The set only holds suits and faces, so the modifiers come from the matching card
+    let restore = |plain: CardData| cards.iter().copied().find(|card| card.same_card(&plain)).unwrap_or(plain);
+    let mut scoring_cards = Vec::with_capacity(evaluation.scoring.len() as usize);
+    for_each_in_order(evaluation.rank, evaluation.scoring, |card| scoring_cards.push(restore(card)));
+    let mut kickers: Vec<CardData> = (set - evaluation.scoring).iter().map(restore).collect();
+    kickers.sort_by_key(|card| Reverse(card.card_face as u8));
+
+    HandEvaluation {
+        rank: evaluation.rank,
+        scoring_cards,
+        kickers,
+        key: evaluation.key,
+    }
+}
+
+/// Classifies a hand that a [`CardSet`] cannot represent: one with more than one copy of a
+/// card, or with Wild cards, which count as every suit, or Stone cards, which have no rank
+/// or suit and always score. Five or more cards of one face still make four of a kind.
+fn evaluate_list(cards: &[CardData], rules: &RuleSet) -> HandEvaluation {
+    let (stones, mut ranked): (Vec<CardData>, Vec<CardData>) = cards.iter().partition(|card| card.is_stone());
+    ranked.sort_by_key(|card| Reverse(card.card_face as u8));
+    let faces_held = |times: usize| {
+        FACES
+            .iter()
+            .filter(|&&face| ranked.iter().filter(|card| card.card_face == face).count() >= times)
+            .fold(0u16, |ranks, &face| ranks | 1 << face as u8)
+    };
+    let pairs = faces_held(2);
+    let trips = faces_held(3);
+    let quads = faces_held(4);
+    let in_suit = |suit: CardSuit| ranked.iter().filter(move |card| card.is_suit(suit));
+    let suit_ranks = |suit: CardSuit| in_suit(suit).fold(0u16, |ranks, card| ranks | 1 << card.card_face as u8);
+    // Wild cards can make a flush in several suits, so take the one with the best cards
+    let flush_suits = || SUITS.into_iter().filter(|&suit| in_suit(suit).count() >= 5);
+    let flush_suit = flush_suits().max_by_key(|&suit| top_faces_key(in_suit(suit)));
+    let straight_flush = flush_suits()
+        .filter_map(|suit| Some((straight_top(suit_ranks(suit), rules.ace_low)?, suit)))
+        .max_by_key(|&(top, _)| top.rank());
+
+    // Up to `per_face` cards of every face in `ranks`, highest face first
+    let take = |ranks: u16, per_face: usize| -> Vec<CardData> {
+        FACES
+            .iter()
+            .rev()
+            .filter(|&&face| ranks & 1 << face as u8 != 0)
+            .flat_map(|&face| ranked.iter().filter(move |card| card.card_face == face).take(per_face))
+            .copied()
+            .collect()
+    };
+    // One card of every face of the straight topped by `top`, from the top down
+    let straight = |top: CardFace, suit: Option<CardSuit>| -> Vec<CardData> {
+        straight_faces(top)
+            .rev()
+            .filter_map(|face| {
+                ranked
+                    .iter()
+                    .find(|card| card.card_face == face && suit.is_none_or(|suit| card.is_suit(suit)))
+            })
+            .copied()
+            .collect()
+    };
+
+    let (rank, mut scoring) = if let Some((top, suit)) = straight_flush {
+        let rank = if top == CardFace::Ace {
+            HandRank::RoyalFlush
+        } else {
+            HandRank::StraightFlush
+        };
+        (rank, straight(top, Some(suit)))
+    } else if quads != 0 {
+        (HandRank::FourOfAKind, take(highest(quads), 4))
+    } else if trips != 0 && pairs & !highest(trips) != 0 {
+        let three = highest(trips);
+        let mut scoring = take(three, 3);
+        scoring.extend(take(highest(pairs & !three), 2));
+        (HandRank::FullHouse, scoring)
+    } else if let Some(suit) = flush_suit {
+        (HandRank::Flush, in_suit(suit).take(5).copied().collect())
+    } else if let Some(top) = straight_top(faces_held(1), rules.ace_low) {
+        (HandRank::Straight, straight(top, None))
+    } else if trips != 0 {
+        (HandRank::ThreeOfAKind, take(highest(trips), 3))
+    } else if pairs.count_ones() >= 2 {
+        (HandRank::TwoPair, take(top_ranks(pairs, 2), 2))
+    } else if pairs != 0 {
+        (HandRank::Pair, take(highest(pairs), 2))
+    } else {
+        (HandRank::HighCard, take(highest(faces_held(1)), 1))
+    };
+
+    let mut kickers = ranked;
+    for card in &scoring {
+        if let Some(position) = kickers.iter().position(|kicker| kicker == card) {
+            kickers.remove(position);
+        }
+    }
+    let key = hand_key(rank, scoring.iter().chain(&kickers).map(|card| card.card_face));
+    scoring.extend(stones);
+
+    HandEvaluation {
+        rank,
+        scoring_cards: scoring,
+        kickers,
+        key,
+    }
+}
+
+/// Orders groups of cards, highest first, by the faces of their top five cards.
+fn top_faces_key<'a>(cards: impl Iterator<Item = &'a CardData>) -> u32 {
+    hand_key(HandRank::HighCard, cards.map(|card| card.card_face))
+}
+
+/// Same key as [`build_set`] works out, from the faces of the scoring cards in order
+/// followed by the kickers.
+fn hand_key(rank: HandRank, faces: impl Iterator<Item = CardFace>) -> u32 {
+    faces
+        .take(5)
+        .zip((0..5).rev())
+        .fold((rank.index() as u32) << 20, |key, (face, slot)| key | (face as u32 + 1) << (4 * slot))
+}
+
+fn build_set(rank: HandRank, cards: CardSet, scoring: CardSet) -> SetEvaluation {
+    // Rank in the top bits followed by up to five faces, each offset by one so an absent card sorts lowest
+    let mut key = (rank.index() as u32) << 20;
+    let mut shift = 20;
+    let mut push = |card: CardData| {
+        if shift > 0 {
+            shift -= 4;
+            key |= (card.card_face as u32 + 1) << shift;
+        }
+    };
+    for_each_in_order(rank, scoring, &mut push);
+    for_each_by_face(cards - scoring, None, &mut push);
+
+    SetEvaluation { rank, scoring, key }
+}
+
+/// Visits the scoring cards strongest group first, or from the top of the straight down.
+fn for_each_in_order(rank: HandRank, scoring: CardSet, mut visit: impl FnMut(CardData)) {
+    match rank {
+        HandRank::Straight | HandRank::StraightFlush | HandRank::RoyalFlush => {
+            if let Some(top) = straight_top(scoring.ranks(), true) {
+                for face in straight_faces(top).rev() {
+                    for card in scoring & CardSet::rank(face) {
+                        visit(card);
+                    }
+                }
+            }
+        }
+        _ => {
+            for size in (1..=4).rev() {
+                for_each_by_face(scoring, Some(size), &mut visit);
+            }
+        }
+    }
+}
+
+/// Visits the cards of every face held exactly `size` times, or of every face when `size`
+/// is `None`, highest face first.
+fn for_each_by_face(cards: CardSet, size: Option<u32>, mut visit: impl FnMut(CardData)) {
+    let mut ranks = cards.ranks();
+    while ranks != 0 {
+        let face = FACES[15 - ranks.leading_zeros() as usize];
+        ranks &= !highest(ranks);
+        let held = cards & CardSet::rank(face);
+        if size.is_none_or(|size| held.len() == size) {
+            held.iter().for_each(&mut visit);
+        }
+    }
+}
+
+/// Spreads a rank mask out by [`CardFace::rank`], so bit `r` is set when a face of rank `r`
+/// is held, and sets bit 1 as well when an Ace is held and can play low.
+fn sequence(ranks: u16, ace_low: bool) -> u16 {
+    let mut sequence = ranks << CardFace::Two.rank();
+    if ace_low && ranks & 1 << CardFace::Ace as u8 != 0 {
+        sequence |= 1 << CardFace::Ace.low_rank();
+    }
+    sequence
+}
+
+/// Highest card of the best straight in a rank mask; a wheel (A-2-3-4-5) tops out at Five.
+fn straight_top(ranks: u16, ace_low: bool) -> Option<CardFace> {
+    let sequence = sequence(ranks, ace_low);
+    let runs = sequence & sequence >> 1 & sequence >> 2 & sequence >> 3 & sequence >> 4;
+    if runs == 0 {
+        return None;
+    }
+    CardFace::from_rank(15 - runs.leading_zeros() as u8 + 4)
+}
+
+/// Faces of the straight topped by `top`, lowest first.
+fn straight_faces(top: CardFace) -> impl DoubleEndedIterator<Item = CardFace> {
+    (top.rank() - 4..=top.rank()).filter_map(CardFace::from_rank)
+}
+
+/// Rank mask of the straight topped by `top`.
+fn straight_ranks(top: CardFace) -> u16 {
+    straight_faces(top).fold(0, |ranks, face| ranks | 1 << face as u8)
+}
+
+/// Highest face in a rank mask, as a mask.
+fn highest(ranks: u16) -> u16 {
+    if ranks == 0 { 0 } else { 1 << (15 - ranks.leading_zeros()) }
+}
+
+/// Highest `count` faces in a rank mask.
+fn top_ranks(mut ranks: u16, count: u32) -> u16 {
+    while ranks.count_ones() > count {
+        ranks &= ranks - 1;
+    }
+    ranks
+}
+
+/// Takes `per_face` cards of every face in `ranks` from `pool`, lowest suit first.
+fn pick(pool: CardSet, ranks: u16, per_face: u32) -> CardSet {
+    let mut picked = CardSet::EMPTY;
+    let mut ranks = ranks;
+    while ranks != 0 {
+        let face = FACES[ranks.trailing_zeros() as usize];
+        ranks &= ranks - 1;
+        let mut held = (pool & CardSet::rank(face)).0;
+        for _ in 0..per_face {
+            picked.0 |= held & held.wrapping_neg();
+            held &= held.wrapping_sub(1);
+        }
+    }
+    picked
+}
@@ -0,0 +1,178 @@
+use crate::card_data::CardData;
+use crate::card_set::CardSet;
+use crate::deck::Deck;
+use crate::distribution::OutcomeDistribution;
+use crate::error::{check_hand, PredictorError};
+use crate::evaluator::{evaluate_hand_with, evaluate_set_with};
+use crate::hand_rank::HandRank;
+use crate::joker::{DiscardStage, Joker, RoundContext};
+use crate::lookup::lookup_five;
+use crate::rules::RuleSet;
+use crate::scoring::{score_round, score_set};
+
+/// Number of cards in a finished hand under the DST rules.
+pub const HAND_SIZE: usize = RuleSet::DST.hand_size;
+
+/// Result of enumerating every replacement draw for a hand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExactOutcome {
+    counts: [u64; HandRank::COUNT],
+    total: u64,
+    score_sum: u64,
+}
+
+impl ExactOutcome {
+    /// Number of draws that finish as `rank`.
+    pub fn count(&self, rank: HandRank) -> u64 {
+        self.counts[rank.index()]
+    }
+
+    /// Number of draws that were enumerated, counting every way of picking duplicate cards.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Exact probability that the finished hand is `rank`.
+    pub fn probability(&self, rank: HandRank) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.count(rank) as f64 / self.total as f64
+    }
+
+    /// Average score of the finished hand over every draw.
+    pub fn expected_score(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.score_sum as f64 / self.total as f64
+    }
+
+    pub fn distribution(&self) -> OutcomeDistribution {
+        OutcomeDistribution::from_probabilities(
+            HandRank::ALL.map(|rank| self.probability(rank)),
+            self.total,
+        )
+    }
+}
+
+/// Enumerates every way of drawing the `kept` cards back up to the hand size of `rules` from
+/// `deck` once `discard` has been thrown away, and tallies the hand and score each draw
+/// finishes with. A draw counts once for every way of picking it from the copies in `deck`.
+///
+/// `discarded_cards` were thrown away earlier in the round; like `discard` they can no
+/// longer be drawn.
+pub fn enumerate_draws(
+    kept: &[CardData],
+    discard: &[CardData],
+    discarded_cards: &[CardData],
+    deck: &Deck,
+    joker: Option<Joker>,
+    rules: &RuleSet,
+) -> anyhow::Result<ExactOutcome> {
+    let mut starting_hand = kept.to_vec();
+    starting_hand.extend_from_slice(discard);
+    check_hand(&starting_hand, discarded_cards, deck, rules)?;
+    rules.check_discard_size(discard.len())?;
+
+    let mut unavailable = starting_hand.clone();
+    unavailable.extend_from_slice(discarded_cards);
+    let available = deck.without(&unavailable)?;
+
+    let draw_count = rules.hand_size - kept.len();
+    let use_lookup = rules.scores_like_dst();
+    // Duplicate cards and modifiers do not fit a CardSet, so those hands are evaluated as a list
+    let use_sets = joker.is_none() && deck.fits_card_set() && kept.iter().all(CardData::is_plain);
+    if available.len() < draw_count {
+        return Err(PredictorError::NotEnoughCards {
+            needed: draw_count,
+            left: available.len(),
+        }
+        .into());
+    }
+
+    let mut stages = Vec::new();
+    if !discarded_cards.is_empty() {
+        stages.push(DiscardStage {
+            discarded: discarded_cards.to_vec(),
+            drawn: Vec::new(),
+        });
+    }
+    stages.push(DiscardStage {
+        discarded: discard.to_vec(),
+        drawn: Vec::new(),
+    });
+
+    let mut outcome = ExactOutcome {
+        counts: [0; HandRank::COUNT],
+        total: 0,
+        score_sum: 0,
+    };
+    let kept_set = CardSet::from(kept);
+    let mut hand = kept.to_vec();
+    available.for_each_draw(draw_count, |drawn, ways| {
+        let (rank, score) = if use_sets {
+            let hand = drawn.iter().fold(kept_set, |set, card| set | CardSet::from_card(card));
+            let entry = if use_lookup { lookup_five(hand) } else { None };
+            match entry {
+                Some(entry) => (entry.rank, entry.score),
+                None => {
+                    let evaluation = evaluate_set_with(hand, rules);
+                    (evaluation.rank, score_set(&evaluation, rules))
+                }
+            }
+        } else {
+            hand.truncate(kept.len());
+            hand.extend_from_slice(drawn);
+            let evaluation = evaluate_hand_with(&hand, rules);
+            let stage = stages.len() - 1;
+            stages[stage].drawn.clear();
+            stages[stage].drawn.extend_from_slice(drawn);
+            let round = RoundContext {
+                starting_hand: &starting_hand,
+                stages: if discard.is_empty() { &stages[..stage] } else { &stages },
+                final_hand: &hand,
+            };
+            (evaluation.rank, score_round(&evaluation, &round, joker, rules))
+        };
+
+        outcome.counts[rank.index()] += ways;
+        outcome.score_sum += score.total as u64 * ways;
+        outcome.total += ways;
+    });
+
+    Ok(outcome)
+}
+
+/// Calls `visit` once for every `k` sized combination of `items`, in lexicographic order.
+pub fn for_each_combination<T: Copy>(items: &[T], k: usize, mut visit: impl FnMut(&[T])) {
+    let n = items.len();
+    if k > n {
+        return;
+    }
+
+    let mut indices: Vec<usize> = (0..k).collect();
+    let mut combination: Vec<T> = indices.iter().map(|&i| items[i]).collect();
+    loop {
+        visit(&combination);
+
+        // Find the right-most index that can still be advanced
+        let mut i = k;
+        loop {
+            if i == 0 {
+                return;
+            }
+            i -= 1;
+            if indices[i] != i + n - k {
+                break;
+            }
+        }
+
+        indices[i] += 1;
+        combination[i] = items[indices[i]];
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+            combination[j] = items[indices[j]];
+        }
+    }
+}
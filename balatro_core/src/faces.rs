@@ -0,0 +1,79 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The face of a card, declared from lowest to highest with the Ace playing high.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CardFace {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    #[default]
+    Ace,
+}
+
+impl CardFace {
+    /// The face with ordinal `rank`, where 2 to 10 are the numbered cards, 11 to 13 are the
+    /// Jack, Queen and King and the Ace is either 1 or 14.
+    pub const fn from_rank(rank: u8) -> Option<Self> {
+        match rank {
+            1 | 14 => Some(CardFace::Ace),
+            2 => Some(CardFace::Two),
+            3 => Some(CardFace::Three),
+            4 => Some(CardFace::Four),
+            5 => Some(CardFace::Five),
+            6 => Some(CardFace::Six),
+            7 => Some(CardFace::Seven),
+            8 => Some(CardFace::Eight),
+            9 => Some(CardFace::Nine),
+            10 => Some(CardFace::Ten),
+            11 => Some(CardFace::Jack),
+            12 => Some(CardFace::Queen),
+            13 => Some(CardFace::King),
+            _ => None,
+        }
+    }
+
+    /// Ordinal used to put cards in sequence, from 2 up to 14 for an Ace playing high.
+    pub const fn rank(&self) -> u8 {
+        *self as u8 + 2
+    }
+
+    /// Ordinal with the Ace playing low as 1, as in A-2-3-4-5.
+    pub const fn low_rank(&self) -> u8 {
+        match self {
+            CardFace::Ace => 1,
+            face => face.rank(),
+        }
+    }
+
+    /// Chips the card is worth, from the card values table in `game_rules.md`.
+    ///
+    /// Jack and Ace are both worth 11, so this is not an ordering; use [`CardFace::rank`].
+    pub const fn value(&self) -> u8 {
+        match self {
+            CardFace::Two => 2,
+            CardFace::Three => 3,
+            CardFace::Four => 4,
+            CardFace::Five => 5,
+            CardFace::Six => 6,
+            CardFace::Seven => 7,
+            CardFace::Eight => 8,
+            CardFace::Nine => 9,
+            CardFace::Ten => 10,
+            CardFace::Ace => 11,
+            CardFace::Jack => 11,
+            CardFace::Queen => 12,
+            CardFace::King => 13,
+        }
+    }
+}
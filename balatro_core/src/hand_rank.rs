@@ -0,0 +1,54 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Every hand type from `game_rules.md`, ordered from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HandRank {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+}
+
+impl HandRank {
+    pub const COUNT: usize = 10;
+    pub const ALL: [HandRank; HandRank::COUNT] = [
+        HandRank::HighCard,
+        HandRank::Pair,
+        HandRank::TwoPair,
+        HandRank::ThreeOfAKind,
+        HandRank::Straight,
+        HandRank::Flush,
+        HandRank::FullHouse,
+        HandRank::FourOfAKind,
+        HandRank::StraightFlush,
+        HandRank::RoyalFlush,
+    ];
+
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Multiplier for the hand type from the table in `game_rules.md`.
+    pub const fn mult(&self) -> u32 {
+        match self {
+            HandRank::HighCard => 1,
+            HandRank::Pair => 2,
+            HandRank::TwoPair => 3,
+            HandRank::ThreeOfAKind => 4,
+            HandRank::Straight => 5,
+            HandRank::Flush => 6,
+            HandRank::FullHouse => 7,
+            HandRank::FourOfAKind => 8,
+            HandRank::StraightFlush => 9,
+            HandRank::RoyalFlush => 10,
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::card_data::CardData;
+use crate::card_suits::CardSuit;
+use crate::evaluator::{evaluate_hand_with, HandEvaluation};
+use crate::faces::CardFace;
+use crate::rules::RuleSet;
+use crate::scoring::Score;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The characters from the joker selector, each bending the score of a round its own way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
+pub enum Joker {
+    /// Each heart discarded +1 mult
+    Maxwell,
+    /// Each pair in hand +3 mult
+    Wilson,
+    /// Each face card discarded +20 chips
+    Willow,
+    /// Each King in hand +25 chips
+    Wolfgang,
+    /// Each card discarded +7 chips
+    Woodie,
+    /// Each Heart or Diamond replaced by a club or spade +2 mult
+    Webber,
+    /// Each Spade in hand +25 chips
+    Wigfrid,
+    /// Each Queen in hand +1 mult
+    Wickerbottom,
+    /// Each heart kept once then discarded +2 mult
+    Wx78,
+    /// Each discard that becomes the same suit +5 chips +2 mult
+    Wendy,
+    /// Hand is worse after discard +30 chips
+    Wes,
+    /// Each heart kept +1 mult
+    Winona,
+    /// Hand contains a heart, a club a diamond and a spade +4 mult
+    Warly,
+    /// Each heart discarded +15 chips Each heart in hand -11 chips +1 mult
+    Wortox,
+    /// Each face card kept +10 chips per face card
+    Wurt,
+    /// Start with 80 chips Each discard -15 chips
+    Wanda,
+    /// Each club kept +15 chips
+    Wormwood,
+    /// For each different suit in discard +15 chips
+    Walter,
+}
+
+/// One discard: the cards thrown away and the replacements drawn for them, in the same order.
+#[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiscardStage {
+    pub discarded: Vec<CardData>,
+    pub drawn: Vec<CardData>,
+}
+
+/// Everything that happened during a round that a joker can look at.
+#[derive(Clone, Copy, Debug)]
+pub struct RoundContext<'a> {
+    pub starting_hand: &'a [CardData],
+    pub stages: &'a [DiscardStage],
+    pub final_hand: &'a [CardData],
+}
+
+impl RoundContext<'_> {
+    fn discarded(&self) -> impl Iterator<Item = &CardData> {
+        self.stages.iter().flat_map(|stage| stage.discarded.iter())
+    }
+
+    /// Cards from the starting hand that were never thrown away.
+    fn kept(&self) -> impl Iterator<Item = &CardData> {
+        self.final_hand
+            .iter()
+            .filter(|card| self.starting_hand.contains(card) && !self.discarded().any(|d| d == *card))
+    }
+
+    /// Discarded cards paired with the card drawn to replace them.
+    fn replacements(&self) -> impl Iterator<Item = (&CardData, &CardData)> {
+        self.stages
+            .iter()
+            .flat_map(|stage| stage.discarded.iter().zip(stage.drawn.iter()))
+    }
+}
+
+impl Joker {
+    pub const ALL: [Joker; 18] = [
+        Joker::Maxwell,
+        Joker::Wilson,
+        Joker::Willow,
+        Joker::Wolfgang,
+        Joker::Woodie,
+        Joker::Webber,
+        Joker::Wigfrid,
+        Joker::Wickerbottom,
+        Joker::Wx78,
+        Joker::Wendy,
+        Joker::Wes,
+        Joker::Winona,
+        Joker::Warly,
+        Joker::Wortox,
+        Joker::Wurt,
+        Joker::Wanda,
+        Joker::Wormwood,
+        Joker::Walter,
+    ];
+
+    /// Adds this joker's chips and mult for `round` on top of `score`, with hands compared
+    /// under `rules`.
+    pub fn apply(&self, round: &RoundContext, evaluation: &HandEvaluation, score: Score, rules: &RuleSet) -> Score {
+        let hearts = |card: &&CardData| card.is_suit(CardSuit::Hearts);
+        let (chips, mult): (i64, i64) = match self {
+            Joker::Maxwell => (0, round.discarded().filter(hearts).count() as i64),
+            Joker::Wilson => {
+                let faces: HashSet<CardFace> = round.final_hand.iter().map(|card| card.card_face).collect();
+                let pairs = faces
+                    .into_iter()
+                    .filter(|face| count_face(round.final_hand, *face) >= 2)
+                    .count();
+                (0, 3 * pairs as i64)
+            }
+            Joker::Willow => (20 * round.discarded().filter(|card| card.is_face_card()).count() as i64, 0),
+            Joker::Wolfgang => (25 * count_face(round.final_hand, CardFace::King), 0),
+            Joker::Woodie => (7 * round.discarded().count() as i64, 0),
+            Joker::Webber => {
+                let replaced = round
+                    .replacements()
+                    .filter(|(discarded, drawn)| is_red(discarded.card_suit) && !is_red(drawn.card_suit))
+                    .count();
+                (0, 2 * replaced as i64)
+            }
+            Joker::Wigfrid => (25 * count_suit(round.final_hand, CardSuit::Spades), 0),
+            Joker::Wickerbottom => (0, count_face(round.final_hand, CardFace::Queen)),
+            Joker::Wx78 => (0, 2 * hearts_kept_then_discarded(round)),
+            Joker::Wendy => {
+                let same_suit = round
+                    .replacements()
+                    .filter(|(discarded, drawn)| discarded.card_suit == drawn.card_suit)
+                    .count() as i64;
+                (5 * same_suit, 2 * same_suit)
+            }
+            Joker::Wes => {
+                let worse = !round.stages.is_empty()
+                    && evaluation.key < evaluate_hand_with(round.starting_hand, rules).key;
+                (if worse { 30 } else { 0 }, 0)
+            }
+            Joker::Winona => (0, round.kept().filter(hearts).count() as i64),
+            Joker::Warly => {
+                let all_suits = [CardSuit::Hearts, CardSuit::Clubs, CardSuit::Diamonds, CardSuit::Spades]
+                    .iter()
+                    .all(|suit| count_suit(round.final_hand, *suit) > 0);
+                (0, if all_suits { 4 } else { 0 })
+            }
+            Joker::Wortox => {
+                let discarded = round.discarded().filter(hearts).count() as i64;
+                let held = count_suit(round.final_hand, CardSuit::Hearts);
+                (15 * discarded - 11 * held, held)
+            }
+            Joker::Wurt => {
+                // Every kept face card is worth 10 chips for each face card in the final hand
+                let kept = round.kept().filter(|card| card.is_face_card()).count() as i64;
+                let held = round.final_hand.iter().filter(|card| card.is_face_card()).count() as i64;
+                (10 * kept * held, 0)
+            }
+            Joker::Wanda => {
+                let discards = round.stages.iter().filter(|stage| !stage.discarded.is_empty()).count();
+                (80 - 15 * discards as i64, 0)
+            }
+            Joker::Wormwood => (15 * round.kept().filter(|card| card.is_suit(CardSuit::Clubs)).count() as i64, 0),
+            Joker::Walter => {
+                let suits = [CardSuit::Hearts, CardSuit::Clubs, CardSuit::Diamonds, CardSuit::Spades]
+                    .iter()
+                    .filter(|suit| round.discarded().any(|card| card.is_suit(**suit)))
+                    .count();
+                (15 * suits as i64, 0)
+            }
+        };
+
+        let chips = (score.chips as i64 + chips).max(0) as u32;
+        Score::new(chips, (score.mult + mult as f64).max(0.0))
+    }
+}
+
+fn is_red(suit: CardSuit) -> bool {
+    suit == CardSuit::Hearts || suit == CardSuit::Diamonds
+}
+
+fn count_face(cards: &[CardData], face: CardFace) -> i64 {
+    cards.iter().filter(|card| card.is_face(face)).count() as i64
+}
+
+fn count_suit(cards: &[CardData], suit: CardSuit) -> i64 {
+    cards.iter().filter(|card| card.is_suit(suit)).count() as i64
+}
+
+/// Hearts that survived one discard and were thrown away in a later one.
+fn hearts_kept_then_discarded(round: &RoundContext) -> i64 {
+    let mut hand = round.starting_hand.to_vec();
+    let mut kept_once: Vec<CardData> = Vec::new();
+    let mut count = 0;
+    for stage in round.stages {
+        count += stage
+            .discarded
+            .iter()
+            .filter(|card| card.is_suit(CardSuit::Hearts) && kept_once.contains(card))
+            .count() as i64;
+
+        hand.retain(|card| !stage.discarded.contains(card));
+        kept_once.extend(hand.iter().filter(|card| card.is_suit(CardSuit::Hearts)));
+        hand.extend_from_slice(&stage.drawn);
+    }
+    count
+}
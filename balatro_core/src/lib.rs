@@ -0,0 +1,22 @@
+pub mod calibration;
+pub mod card_data;
+pub mod card_set;
+pub mod card_suits;
+pub mod balatro_predictor;
+pub mod faces;
+pub mod deck;
+pub mod distribution;
+pub mod error;
+pub mod evaluator;
+pub mod exact;
+pub mod hand_rank;
+pub mod joker;
+pub mod lookup;
+pub mod modifiers;
+pub mod monte_carlo;
+pub mod notation;
+pub mod optimizer;
+pub mod planner;
+pub mod rules;
+pub mod scoring;
+pub mod session;
\ No newline at end of file
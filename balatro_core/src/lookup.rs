@@ -0,0 +1,129 @@
+use crate::card_data::CardData;
+use crate::card_set::{CardSet, ALL_RANKS, FACES, SUITS};
+use crate::evaluator::evaluate_set;
+use crate::hand_rank::HandRank;
+use crate::rules::RuleSet;
+use crate::scoring::{score_set, Score};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// One prime per face in [`FACES`] order, so the product of a hand's primes identifies
+/// its faces regardless of order.
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Hand type and score of a five card hand under [`RuleSet::DST`], as stored in the lookup tables.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TableEntry {
+    pub rank: HandRank,
+    pub score: Score,
+}
+
+/// Tables covering every five card hand, built the first time they are used.
+struct Tables {
+    /// Five cards of one suit, indexed by their rank mask.
+    flush: Vec<Option<TableEntry>>,
+    /// Five different faces of mixed suits, indexed by their rank mask.
+    distinct: Vec<Option<TableEntry>>,
+    /// Hands with a repeated face, keyed by the product of their primes.
+    repeated: HashMap<u32, TableEntry>,
+}
+
+static TABLES: LazyLock<Tables> = LazyLock::new(build_tables);
+
+/// Looks up the hand type and score of exactly five cards in constant time.
+///
+/// Returns `None` when `cards` does not hold five cards.
+pub fn lookup_five(cards: CardSet) -> Option<TableEntry> {
+    if cards.len() != 5 {
+        return None;
+    }
+
+    let tables = &*TABLES;
+    for suit in SUITS {
+        let suited = cards.suit_ranks(suit);
+        if suited.count_ones() == 5 {
+            return tables.flush[suited as usize];
+        }
+    }
+
+    let ranks = cards.ranks();
+    if ranks.count_ones() == 5 {
+        return tables.distinct[ranks as usize];
+    }
+
+    let product = cards.iter().map(|card| PRIMES[card.card_face as usize]).product();
+    tables.repeated.get(&product).copied()
+}
+
+/// Hand type of `cards`, from the lookup tables when it holds five cards.
+pub fn lookup_rank(cards: CardSet) -> HandRank {
+    match lookup_five(cards) {
+        Some(entry) => entry.rank,
+        None => evaluate_set(cards).rank,
+    }
+}
+
+fn build_tables() -> Tables {
+    let entry = |cards: CardSet| {
+        let evaluation = evaluate_set(cards);
+        TableEntry {
+            rank: evaluation.rank,
+            score: score_set(&evaluation, &RuleSet::DST),
+        }
+    };
+    let suited = |ranks: u16, suit: usize| CardSet(((ranks & ALL_RANKS) as u64) << (13 * suit));
+
+    let mut flush = vec![None; 1 << 13];
+    let mut distinct = vec![None; 1 << 13];
+    for ranks in 0..1u16 << 13 {
+        if ranks.count_ones() != 5 {
+            continue;
+        }
+        flush[ranks as usize] = Some(entry(suited(ranks, 0)));
+
+        // Move the lowest face to another suit so the five cannot make a flush
+        let lowest = ranks & ranks.wrapping_neg();
+        distinct[ranks as usize] = Some(entry(suited(ranks & !lowest, 0) | suited(lowest, 1)));
+    }
+
+    let mut repeated = HashMap::new();
+    let mut counts = [0usize; 13];
+    add_repeated(&mut counts, 0, 5, &mut |counts: &[usize; 13]| {
+        if counts.iter().all(|&count| count <= 1) {
+            return;
+        }
+        // The n-th copy of a face goes to the n-th suit, so no two cards collide
+        let cards: CardSet = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(face, &count)| {
+                (0..count).map(move |suit| CardData::new(FACES[face], SUITS[suit]))
+            })
+            .collect();
+        let product = cards.iter().map(|card| PRIMES[card.card_face as usize]).product();
+        repeated.insert(product, entry(cards));
+    });
+
+    Tables {
+        flush,
+        distinct,
+        repeated,
+    }
+}
+
+/// Visits every way of sharing `left` cards between the faces from `face` up, at most four
+/// of each.
+fn add_repeated(counts: &mut [usize; 13], face: usize, left: usize, visit: &mut impl FnMut(&[usize; 13])) {
+    if left == 0 {
+        visit(counts);
+        return;
+    }
+    if face == counts.len() {
+        return;
+    }
+    for count in (0..=left.min(4)).rev() {
+        counts[face] = count;
+        add_repeated(counts, face + 1, left - count, visit);
+    }
+    counts[face] = 0;
+}
@@ -0,0 +1,55 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Balatro's card enhancements. A card has at most one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Enhancement {
+    /// +30 chips when scored.
+    Bonus,
+    /// +4 mult when scored.
+    Mult,
+    /// Counts as every suit.
+    Wild,
+    /// ×2 mult when scored.
+    Glass,
+    /// ×1.5 mult while held in hand.
+    Steel,
+    /// 50 chips and no rank or suit; always scores.
+    Stone,
+    /// Pays money while held in hand, which does not change the score.
+    Gold,
+    /// 1 in 5 chance of +20 mult when scored, counted as its expected +4.
+    Lucky,
+}
+
+/// Balatro's card editions; a card without one is the base edition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Edition {
+    /// +50 chips when scored.
+    Foil,
+    /// +10 mult when scored.
+    Holographic,
+    /// ×1.5 mult when scored.
+    Polychrome,
+}
+
+/// Balatro's card seals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Seal {
+    /// Triggers the card a second time, whether scored or held.
+    Red,
+    /// Creates a planet card, which does not change the score.
+    Blue,
+    /// Pays money when scored, which does not change the score.
+    Gold,
+    /// Creates a tarot card when discarded, which does not change the score.
+    Purple,
+}
+
+impl Enhancement {
+    /// Chips a Stone card scores in place of its face value.
+    pub const STONE_CHIPS: u32 = 50;
+}
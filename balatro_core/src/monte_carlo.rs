@@ -0,0 +1,488 @@
+use crate::balatro_predictor::kept_after_discard;
+use crate::card_data::{BalatroPredictor, CardData};
+use crate::card_set::CardSet;
+use crate::deck::Deck;
+use crate::distribution::OutcomeDistribution;
+use crate::error::{check_hand, PredictorError};
+use crate::evaluator::{evaluate_hand_with, evaluate_set_with};
+use crate::hand_rank::HandRank;
+use crate::joker::{DiscardStage, Joker, RoundContext};
+use crate::lookup::lookup_five;
+use crate::optimizer::{discard_masks, score_masks, subset, DiscardOption};
+use crate::planner::{sampled_plan_config, WORK_LIMIT};
+use crate::rules::RuleSet;
+use crate::scoring::{score_round, score_set};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// z value of a two sided 95% confidence interval.
+const Z_95: f64 = 1.959964;
+
+/// Samples drawn between checks against the tolerance.
+const BATCH_SIZE: u64 = 1_000;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MonteCarloConfig {
+    /// Seed for the shuffles; a random seed is picked when left empty.
+    pub seed: Option<u64>,
+    pub max_samples: u64,
+    /// Stop early once every hand type's 95% interval is narrower than this on either side.
+    pub tolerance: Option<f64>,
+    /// Draws used to compare the options of every later discard.
+    pub inner_samples: u32,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        MonteCarloConfig {
+            seed: None,
+            max_samples: 100_000,
+            tolerance: None,
+            inner_samples: 64,
+        }
+    }
+}
+
+/// A sampled value with its 95% confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Estimate {
+    pub mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub samples: u64,
+}
+
+impl Estimate {
+    /// Wilson score interval for `hits` successes out of `samples`.
+    fn proportion(hits: u64, samples: u64) -> Self {
+        if samples == 0 {
+            return Estimate {
+                mean: 0.0,
+                lower: 0.0,
+                upper: 1.0,
+                samples,
+            };
+        }
+
+        let n = samples as f64;
+        let p = hits as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half_width = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        Estimate {
+            mean: p,
+            lower: (center - half_width).max(0.0),
+            upper: (center + half_width).min(1.0),
+            samples,
+        }
+    }
+
+    /// Normal interval around the mean of `samples` values with the given sums.
+    fn mean(sum: f64, sum_of_squares: f64, samples: u64) -> Self {
+        if samples == 0 {
+            return Estimate {
+                mean: 0.0,
+                lower: 0.0,
+                upper: 0.0,
+                samples,
+            };
+        }
+
+        let n = samples as f64;
+        let mean = sum / n;
+        let variance = if samples > 1 {
+            ((sum_of_squares - sum * mean) / (n - 1.0)).max(0.0)
+        } else {
+            0.0
+        };
+        let half_width = Z_95 * (variance / n).sqrt();
+        Estimate {
+            mean,
+            lower: mean - half_width,
+            upper: mean + half_width,
+            samples,
+        }
+    }
+
+    pub fn half_width(&self) -> f64 {
+        (self.upper - self.lower) / 2.0
+    }
+}
+
+/// Sampled outcome of a discard, with a confidence interval on every number.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SampledOutcome {
+    pub distribution: OutcomeDistribution,
+    pub probabilities: BTreeMap<HandRank, Estimate>,
+    /// Expected chips × mult score of the finished hand.
+    pub expected_score: Estimate,
+    pub samples: u64,
+}
+
+/// Throws away `discard` from `hand`, then plays out random draws until the hand locks.
+///
+/// After the first draw every one of the `discards_after` later discards is chosen by
+/// comparing each option over [`MonteCarloConfig::inner_samples`] draws of its own.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    discard: &[CardData],
+    discards_after: u8,
+    deck: &Deck,
+    joker: Option<Joker>,
+    config: &MonteCarloConfig,
+    rules: &RuleSet,
+) -> anyhow::Result<SampledOutcome> {
+    check_hand(hand, discarded_cards, deck, rules)?;
+    rules.check_discard_size(discard.len())?;
+    let kept = kept_after_discard(hand, discard)?;
+    let mut removed = discarded_cards.to_vec();
+    removed.extend_from_slice(discard);
+    let mut unavailable = kept.clone();
+    unavailable.extend_from_slice(&removed);
+    let left = deck.without(&unavailable)?.len();
+    let draw_count = rules.hand_size.saturating_sub(kept.len());
+    if left < draw_count {
+        return Err(PredictorError::NotEnoughCards {
+            needed: draw_count,
+            left,
+        }
+        .into());
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed.unwrap_or_else(rand::random));
+    let mut counts = [0u64; HandRank::COUNT];
+    let mut score_sum = 0.0;
+    let mut score_sum_of_squares = 0.0;
+    let mut samples = 0u64;
+    let mut round = SampledRound {
+        joker,
+        rules,
+        deck,
+        starting_hand: hand.to_vec(),
+        stages: Vec::new(),
+        inner_samples: config.inner_samples,
+        use_lookup: rules.scores_like_dst(),
+    };
+    if !discarded_cards.is_empty() {
+        round.stages.push(DiscardStage {
+            discarded: discarded_cards.to_vec(),
+            drawn: Vec::new(),
+        });
+    }
+
+    let mut available = round.available(&kept, &removed);
+    while samples < config.max_samples {
+        let batch_end = (samples + BATCH_SIZE).min(config.max_samples);
+        while samples < batch_end {
+            let (rank, score) = round.play_out(&kept, discard, &mut available, &removed, discards_after, &mut rng);
+            counts[rank.index()] += 1;
+            score_sum += score;
+            score_sum_of_squares += score * score;
+            samples += 1;
+        }
+
+        if let Some(tolerance) = config.tolerance {
+            let converged = counts
+                .iter()
+                .all(|&hits| Estimate::proportion(hits, samples).half_width() < tolerance);
+            if converged {
+                break;
+            }
+        }
+    }
+
+    let probabilities: BTreeMap<HandRank, Estimate> = HandRank::ALL
+        .iter()
+        .map(|&rank| (rank, Estimate::proportion(counts[rank.index()], samples)))
+        .collect();
+    Ok(SampledOutcome {
+        distribution: OutcomeDistribution::from_probabilities(
+            HandRank::ALL.map(|rank| probabilities[&rank].mean),
+            samples,
+        ),
+        probabilities,
+        expected_score: Estimate::mean(score_sum, score_sum_of_squares, samples),
+        samples,
+    })
+}
+
+/// Sampled counterpart of [`crate::planner::plan_discards`]: scores every first
+/// discard from `hand` and returns them best first.
+pub fn simulate_plan(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    discards_remaining: u8,
+    deck: &Deck,
+    joker: Option<Joker>,
+    config: &MonteCarloConfig,
+    rules: &RuleSet,
+) -> anyhow::Result<Vec<DiscardOption>> {
+    rules.check_discards_remaining(discards_remaining)?;
+    // Every option shares one seed so they are compared over the same shuffles
+    let config = MonteCarloConfig {
+        seed: Some(config.seed.unwrap_or_else(rand::random)),
+        ..config.clone()
+    };
+
+    let masks: Vec<u32> = if discards_remaining == 0 {
+        vec![0]
+    } else {
+        discard_masks(hand, rules).collect()
+    };
+    let mut options = score_masks(masks.into_iter(), |mask| {
+        let discard = subset(hand, mask);
+        let kept = subset(hand, !mask);
+        // Keeping every card locks the hand in, as in the exact planner
+        let discards_after = if mask == 0 { 0 } else { discards_remaining - 1 };
+        let outcome = simulate(
+            hand,
+            discarded_cards,
+            &discard,
+            discards_after,
+            deck,
+            joker,
+            &config,
+            rules,
+        )?;
+        Ok(DiscardOption {
+            discard,
+            kept,
+            distribution: outcome.distribution,
+            expected_score: outcome.expected_score.mean as f32,
+            samples: Some(outcome.samples),
+        })
+    })?;
+
+    // Best expected score first, preferring to throw away fewer cards when tied
+    options.sort_by(|a, b| {
+        b.expected_score
+            .total_cmp(&a.expected_score)
+            .then(a.discard.len().cmp(&b.discard.len()))
+    });
+    Ok(options)
+}
+
+/// The round played so far in one sample, which the joker scores the finished hand against.
+struct SampledRound<'a> {
+    joker: Option<Joker>,
+    rules: &'a RuleSet,
+    deck: &'a Deck,
+    starting_hand: Vec<CardData>,
+    stages: Vec<DiscardStage>,
+    inner_samples: u32,
+    use_lookup: bool,
+}
+
+impl SampledRound<'_> {
+    fn score(&self, hand: &[CardData]) -> (HandRank, f64) {
+        // Plain cards scored without a joker fit a CardSet, as in the exact engine
+        let set = CardSet::from(hand);
+        if self.joker.is_none() && set.len() as usize == hand.len() && hand.iter().all(CardData::is_plain) {
+            let entry = if self.use_lookup { lookup_five(set) } else { None };
+            return match entry {
+                Some(entry) => (entry.rank, entry.score.total as f64),
+                None => {
+                    let evaluation = evaluate_set_with(set, self.rules);
+                    (evaluation.rank, score_set(&evaluation, self.rules).total as f64)
+                }
+            };
+        }
+
+        let evaluation = evaluate_hand_with(hand, self.rules);
+        let round = RoundContext {
+            starting_hand: &self.starting_hand,
+            stages: &self.stages,
+            final_hand: hand,
+        };
+        let score = score_round(&evaluation, &round, self.joker, self.rules).total as f64;
+        (evaluation.rank, score)
+    }
+
+    /// The cards that can still be drawn once `kept` and `removed` are out of the deck.
+    fn available(&self, kept: &[CardData], removed: &[CardData]) -> Vec<CardData> {
+        let mut unavailable = kept.to_vec();
+        unavailable.extend_from_slice(removed);
+        self.deck.remaining(&unavailable).cards()
+    }
+
+    /// Draws random cards from `available` to fill `kept` back up to the hand size, plays
+    /// any later discards and scores the hand it finishes with. `removed` holds every card
+    /// thrown away so far, `discard` included, and `available` is only reordered, so it can
+    /// be drawn from again for the next sample.
+    fn play_out(
+        &mut self,
+        kept: &[CardData],
+        discard: &[CardData],
+        available: &mut [CardData],
+        removed: &[CardData],
+        discards_after: u8,
+        rng: &mut StdRng,
+    ) -> (HandRank, f64) {
+        let draw_count = self.rules.hand_size.saturating_sub(kept.len());
+        let (drawn, _) = available.partial_shuffle(rng, draw_count);
+
+        let mut hand = kept.to_vec();
+        hand.extend_from_slice(drawn);
+        // Only a joker looks back at the discards
+        let staged = self.joker.is_some() && !discard.is_empty();
+        if staged {
+            self.stages.push(DiscardStage {
+                discarded: discard.to_vec(),
+                drawn: drawn.to_vec(),
+            });
+        }
+
+        let result = self.finish(hand, removed, discards_after, rng);
+        if staged {
+            self.stages.pop();
+        }
+        result
+    }
+
+    /// Compares every follow-up discard from `hand` over a handful of draws and plays the best one.
+    fn finish(
+        &mut self,
+        hand: Vec<CardData>,
+        removed: &[CardData],
+        discards_after: u8,
+        rng: &mut StdRng,
+    ) -> (HandRank, f64) {
+        let stand = self.score(&hand);
+        if discards_after == 0 {
+            return stand;
+        }
+
+        // Whichever cards are thrown away, the draws come from what is left besides the hand
+        let mut available = self.available(&hand, removed);
+        let mut best_mask = 0;
+        let mut best_score = stand.1;
+        for mask in discard_masks(&hand, self.rules).skip(1) {
+            let next_kept = subset(&hand, !mask);
+            let next_discard = subset(&hand, mask);
+            let mut next_removed = removed.to_vec();
+            next_removed.extend_from_slice(&next_discard);
+
+            let score = (0..self.inner_samples)
+                .map(|_| self.play_out(&next_kept, &next_discard, &mut available, &next_removed, 0, rng).1)
+                .sum::<f64>()
+                / self.inner_samples.max(1) as f64;
+            if score > best_score {
+                best_score = score;
+                best_mask = mask;
+            }
+        }
+
+        if best_mask == 0 {
+            return stand;
+        }
+        let next_kept = subset(&hand, !best_mask);
+        let next_discard = subset(&hand, best_mask);
+        let mut next_removed = removed.to_vec();
+        next_removed.extend_from_slice(&next_discard);
+        self.play_out(&next_kept, &next_discard, &mut available, &next_removed, discards_after - 1, rng)
+    }
+}
+
+/// A [`BalatroPredictor`] that samples draws instead of enumerating them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonteCarloPredictor {
+    pub hand: Vec<CardData>,
+    pub config: MonteCarloConfig,
+    pub deck: Deck,
+    pub rules: RuleSet,
+}
+
+impl MonteCarloPredictor {
+    pub fn new(hand: Vec<CardData>, config: MonteCarloConfig, deck: Deck, rules: RuleSet) -> Self {
+        MonteCarloPredictor {
+            hand,
+            config,
+            deck,
+            rules,
+        }
+    }
+}
+
+impl BalatroPredictor for MonteCarloPredictor {
+    fn calculate_outcome_distribution(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+    ) -> anyhow::Result<OutcomeDistribution> {
+        let outcome = simulate(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            &discard.unwrap_or_default(),
+            0,
+            &self.deck,
+            None,
+            &self.config,
+            &self.rules,
+        )?;
+        Ok(outcome.distribution)
+    }
+
+    fn calculate_expected_score(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discard: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<f32> {
+        let outcome = simulate(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            &discard.unwrap_or_default(),
+            0,
+            &self.deck,
+            joker,
+            &self.config,
+            &self.rules,
+        )?;
+        Ok(outcome.expected_score.mean as f32)
+    }
+
+    fn optimize_discard(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        simulate_plan(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            1,
+            &self.deck,
+            joker,
+            &self.config,
+            &self.rules,
+        )
+    }
+
+    fn plan_discards(
+        &mut self,
+        discarded_cards: Option<Vec<CardData>>,
+        discards_remaining: u8,
+        joker: Option<Joker>,
+    ) -> anyhow::Result<Vec<DiscardOption>> {
+        // Never sample more than an exact plan is allowed to score
+        let config = sampled_plan_config(&self.hand, discards_remaining, &self.rules, &self.config, WORK_LIMIT);
+        simulate_plan(
+            &self.hand,
+            &discarded_cards.unwrap_or_default(),
+            discards_remaining,
+            &self.deck,
+            joker,
+            &config,
+            &self.rules,
+        )
+    }
+}
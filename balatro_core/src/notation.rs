@@ -0,0 +1,267 @@
+use crate::card_data::CardData;
+use crate::card_suits::CardSuit;
+use crate::faces::CardFace;
+#[cfg(feature = "serde")]
+use crate::modifiers::{Edition, Enhancement, Seal};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// Why a card in text notation could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseCardErrorKind {
+    Empty,
+    UnknownFace,
+    UnknownSuit,
+}
+
+/// A card that could not be read, pointing at the token it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCardError {
+    pub kind: ParseCardErrorKind,
+    /// The text of the offending card.
+    pub token: String,
+    /// Byte offset of the token in the parsed text.
+    pub position: usize,
+}
+
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let problem = match self.kind {
+            ParseCardErrorKind::Empty => "expected a card",
+            ParseCardErrorKind::UnknownFace => "unknown face, expected 2-10, T, J, Q, K or A",
+            ParseCardErrorKind::UnknownSuit => "unknown suit, expected c, d, h, s or ♣ ♦ ♥ ♠",
+        };
+        write!(f, "invalid card {:?} at position {}: {}", self.token, self.position, problem)
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl Display for CardFace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let face = match self {
+            CardFace::Two => "2",
+            CardFace::Three => "3",
+            CardFace::Four => "4",
+            CardFace::Five => "5",
+            CardFace::Six => "6",
+            CardFace::Seven => "7",
+            CardFace::Eight => "8",
+            CardFace::Nine => "9",
+            CardFace::Ten => "10",
+            CardFace::Jack => "J",
+            CardFace::Queen => "Q",
+            CardFace::King => "K",
+            CardFace::Ace => "A",
+        };
+        f.write_str(face)
+    }
+}
+
+impl FromStr for CardFace {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.to_ascii_uppercase().as_str() {
+            "2" => Ok(CardFace::Two),
+            "3" => Ok(CardFace::Three),
+            "4" => Ok(CardFace::Four),
+            "5" => Ok(CardFace::Five),
+            "6" => Ok(CardFace::Six),
+            "7" => Ok(CardFace::Seven),
+            "8" => Ok(CardFace::Eight),
+            "9" => Ok(CardFace::Nine),
+            "10" | "T" => Ok(CardFace::Ten),
+            "J" => Ok(CardFace::Jack),
+            "Q" => Ok(CardFace::Queen),
+            "K" => Ok(CardFace::King),
+            "A" => Ok(CardFace::Ace),
+            _ => Err(ParseCardError {
+                kind: ParseCardErrorKind::UnknownFace,
+                token: text.to_string(),
+                position: 0,
+            }),
+        }
+    }
+}
+
+impl Display for CardSuit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let suit = match self {
+            CardSuit::Clubs => "c",
+            CardSuit::Diamonds => "d",
+            CardSuit::Hearts => "h",
+            CardSuit::Spades => "s",
+        };
+        f.write_str(suit)
+    }
+}
+
+impl FromStr for CardSuit {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "c" | "C" | "♣" | "♧" => Ok(CardSuit::Clubs),
+            "d" | "D" | "♦" | "♢" => Ok(CardSuit::Diamonds),
+            "h" | "H" | "♥" | "♡" => Ok(CardSuit::Hearts),
+            "s" | "S" | "♠" | "♤" => Ok(CardSuit::Spades),
+            _ => Err(ParseCardError {
+                kind: ParseCardErrorKind::UnknownSuit,
+                token: text.to_string(),
+                position: 0,
+            }),
+        }
+    }
+}
+
+/// Formats a card as its face followed by its suit, such as `Ah` or `10s`.
+impl Display for CardData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.card_face, self.card_suit)
+    }
+}
+
+/// Reads a card such as `Ah`, `10s`, `Td` or `K♥`.
+impl FromStr for CardData {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let token = text.trim();
+        let error = |kind| ParseCardError {
+            kind,
+            token: token.to_string(),
+            position: 0,
+        };
+
+        // The suit is always the last character, whether a letter or a symbol
+        let (split, _) = token
+            .char_indices()
+            .last()
+            .ok_or_else(|| error(ParseCardErrorKind::Empty))?;
+        let (face, suit) = token.split_at(split);
+        Ok(CardData::new(
+            face.parse().map_err(|e: ParseCardError| error(e.kind))?,
+            suit.parse().map_err(|e: ParseCardError| error(e.kind))?,
+        ))
+    }
+}
+
+/// The forms a card is accepted in when deserializing: the struct with both enums and any
+/// modifiers, or text notation such as `"Ah"`.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum CardNotation {
+    Text(String),
+    Fields {
+        card_suit: CardSuit,
+        card_face: CardFace,
+        #[serde(default)]
+        enhancement: Option<Enhancement>,
+        #[serde(default)]
+        edition: Option<Edition>,
+        #[serde(default)]
+        seal: Option<Seal>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CardNotation> for CardData {
+    type Error = ParseCardError;
+
+    fn try_from(notation: CardNotation) -> Result<Self, Self::Error> {
+        match notation {
+            CardNotation::Text(text) => text.parse(),
+            CardNotation::Fields {
+                card_suit,
+                card_face,
+                enhancement,
+                edition,
+                seal,
+            } => Ok(CardData {
+                card_suit,
+                card_face,
+                enhancement,
+                edition,
+                seal,
+            }),
+        }
+    }
+}
+
+/// A list of cards in text notation, such as `As Kh 10d 4c 4s`.
+///
+/// Cards are separated by spaces or commas and written back separated by spaces.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hand(pub Vec<CardData>);
+
+impl Deref for Hand {
+    type Target = Vec<CardData>;
+
+    fn deref(&self) -> &Vec<CardData> {
+        &self.0
+    }
+}
+
+impl DerefMut for Hand {
+    fn deref_mut(&mut self) -> &mut Vec<CardData> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<CardData>> for Hand {
+    fn from(cards: Vec<CardData>) -> Self {
+        Hand(cards)
+    }
+}
+
+impl From<Hand> for Vec<CardData> {
+    fn from(hand: Hand) -> Self {
+        hand.0
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut cards = Vec::new();
+        let mut start = None;
+        for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            let separator = c.is_whitespace() || c == ',';
+            match (start, separator) {
+                (None, false) => start = Some(index),
+                (Some(from), true) => {
+                    let card = text[from..index].parse().map_err(|error: ParseCardError| ParseCardError {
+                        position: from,
+                        ..error
+                    })?;
+                    cards.push(card);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(Hand(cards))
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        format_cards(&self.0).fmt(f)
+    }
+}
+
+/// Writes `cards` in text notation separated by spaces.
+pub fn format_cards(cards: &[CardData]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,90 @@
+use crate::balatro_predictor::kept_after_discard;
+use crate::card_data::CardData;
+use crate::deck::Deck;
+use crate::error::check_hand;
+use crate::distribution::OutcomeDistribution;
+use crate::exact::enumerate_draws;
+use crate::joker::Joker;
+use crate::rules::RuleSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One way of discarding from a hand, scored against what is left of the deck.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiscardOption {
+    pub discard: Vec<CardData>,
+    pub kept: Vec<CardData>,
+    pub distribution: OutcomeDistribution,
+    /// Expected chips × mult score of the finished hand.
+    pub expected_score: f32,
+    /// Draws the option was sampled over, or `None` when every draw was counted exactly.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub samples: Option<u64>,
+}
+
+/// Scores every subset of `hand` that `rules` allow to be thrown away, keeping everything
+/// included, and returns them best first.
+pub fn optimize_discard(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    deck: &Deck,
+    joker: Option<Joker>,
+    rules: &RuleSet,
+) -> anyhow::Result<Vec<DiscardOption>> {
+    check_hand(hand, discarded_cards, deck, rules)?;
+    let mut options = score_masks(discard_masks(hand, rules), |mask| {
+        let discard = subset(hand, mask);
+        let kept = kept_after_discard(hand, &discard)?;
+        let outcome = enumerate_draws(&kept, &discard, discarded_cards, deck, joker, rules)?;
+
+        Ok(DiscardOption {
+            discard,
+            kept,
+            distribution: outcome.distribution(),
+            expected_score: outcome.expected_score() as f32,
+            samples: None,
+        })
+    })?;
+
+    // Best expected score first, preferring to throw away fewer cards when tied
+    options.sort_by(|a, b| {
+        b.expected_score
+            .total_cmp(&a.expected_score)
+            .then(a.discard.len().cmp(&b.discard.len()))
+    });
+    Ok(options)
+}
+
+/// The cards of `hand` whose positions are set in `mask`.
+pub(crate) fn subset(hand: &[CardData], mask: u32) -> Vec<CardData> {
+    hand.iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, card)| *card)
+        .collect()
+}
+
+/// Calls `score` on every mask, spread over every core with the `parallel` feature, and
+/// returns the results in the order of `masks`.
+pub(crate) fn score_masks<T: Send>(
+    masks: impl Iterator<Item = u32>,
+    score: impl Fn(u32) -> anyhow::Result<T> + Send + Sync,
+) -> anyhow::Result<Vec<T>> {
+    let masks: Vec<u32> = masks.collect();
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        masks.into_par_iter().map(score).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        masks.into_iter().map(score).collect()
+    }
+}
+
+/// Every mask over `hand` that discards no more cards than `rules` allow at once.
+pub(crate) fn discard_masks(hand: &[CardData], rules: &RuleSet) -> impl Iterator<Item = u32> + use<> {
+    let max_discard_size = rules.max_discard_size;
+    (0..1u32 << hand.len()).filter(move |mask| mask.count_ones() as usize <= max_discard_size)
+}
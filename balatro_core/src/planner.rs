@@ -0,0 +1,308 @@
+use crate::balatro_predictor::kept_after_discard;
+use crate::card_data::CardData;
+use crate::deck::Deck;
+use crate::distribution::OutcomeDistribution;
+use crate::error::{check_hand, PredictorError};
+use crate::evaluator::evaluate_hand_with;
+use crate::hand_rank::HandRank;
+use crate::joker::{DiscardStage, Joker, RoundContext};
+use crate::monte_carlo::{simulate_plan, MonteCarloConfig};
+use crate::optimizer::{discard_masks, score_masks, subset, DiscardOption};
+use crate::rules::RuleSet;
+use crate::scoring::score_round;
+
+/// Expected result of playing a position optimally from here on.
+#[derive(Clone, Copy)]
+struct PlayValue {
+    expected_score: f64,
+    probabilities: [f64; HandRank::COUNT],
+}
+
+/// Hands [`plan_discards`] may score to solve a plan exactly before sampling it instead.
+pub const WORK_LIMIT: u64 = 10_000_000;
+
+/// Draws a sampled plan uses to compare the options of every later discard.
+const SAMPLED_INNER_SAMPLES: u32 = 16;
+
+/// Draws a sampled plan scores each option over before it compares later discards over
+/// fewer draws.
+const MIN_SAMPLES: u64 = 32;
+
+/// Scores every first discard from `hand` assuming every later discard is played to
+/// maximise the expected score, and returns them best first.
+///
+/// This is solved by backward induction over every draw, so the work grows with the
+/// deck size raised to the number of discards. Plans that would score more than
+/// [`WORK_LIMIT`] hands, such as two discards against a full deck, are sampled with
+/// [`simulate_plan`] instead, using a fixed seed and as many samples as the limit allows.
+/// Their options have [`DiscardOption::samples`] set, so callers can tell them apart.
+pub fn plan_discards(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    discards_remaining: u8,
+    deck: &Deck,
+    joker: Option<Joker>,
+    rules: &RuleSet,
+) -> anyhow::Result<Vec<DiscardOption>> {
+    plan_discards_within(hand, discarded_cards, discards_remaining, deck, joker, rules, WORK_LIMIT)
+}
+
+/// [`plan_discards`] with the number of hands it may score set by `work_limit`.
+pub fn plan_discards_within(
+    hand: &[CardData],
+    discarded_cards: &[CardData],
+    discards_remaining: u8,
+    deck: &Deck,
+    joker: Option<Joker>,
+    rules: &RuleSet,
+    work_limit: u64,
+) -> anyhow::Result<Vec<DiscardOption>> {
+    rules.check_discards_remaining(discards_remaining)?;
+    check_hand(hand, discarded_cards, deck, rules)?;
+
+    let mut unavailable = hand.to_vec();
+    unavailable.extend_from_slice(discarded_cards);
+    let left = deck.remaining(&unavailable).len();
+    if exact_work(hand.len(), left, discards_remaining, rules) > work_limit as f64 {
+        let config = MonteCarloConfig {
+            seed: Some(0),
+            max_samples: u64::MAX,
+            tolerance: None,
+            inner_samples: SAMPLED_INNER_SAMPLES,
+        };
+        let config = sampled_plan_config(hand, discards_remaining, rules, &config, work_limit);
+        return simulate_plan(hand, discarded_cards, discards_remaining, deck, joker, &config, rules);
+    }
+
+    let mut round = Round {
+        joker,
+        rules,
+        deck,
+        starting_hand: hand.to_vec(),
+        stages: Vec::new(),
+    };
+    if !discarded_cards.is_empty() {
+        round.stages.push(DiscardStage {
+            discarded: discarded_cards.to_vec(),
+            drawn: Vec::new(),
+        });
+    }
+
+    if discards_remaining == 0 {
+        // A short hand is still drawn up to full before it is played
+        let (value, draws) = round.discard_value(hand, &[], discarded_cards, 0);
+        return Ok(vec![DiscardOption {
+            discard: Vec::new(),
+            kept: hand.to_vec(),
+            distribution: OutcomeDistribution::from_probabilities(value.probabilities, draws),
+            expected_score: value.expected_score as f32,
+            samples: None,
+        }]);
+    }
+
+    let mut options = score_masks(discard_masks(hand, rules), |mask| {
+        // Every option plays out its own copy of the round
+        let mut round = round.clone();
+        let discard = subset(hand, mask);
+        let kept = kept_after_discard(hand, &discard)?;
+        let mut removed = discarded_cards.to_vec();
+        removed.extend_from_slice(&discard);
+
+        let mut unavailable = kept.clone();
+        unavailable.extend_from_slice(&removed);
+        let left = deck.without(&unavailable)?.len();
+        let draw_count = rules.hand_size.saturating_sub(kept.len());
+        if left < draw_count {
+            return Err(PredictorError::NotEnoughCards {
+                needed: draw_count,
+                left,
+            }
+            .into());
+        }
+
+        // Keeping every card locks the hand in as soon as it is full
+        let discards_after = if discard.is_empty() { 0 } else { discards_remaining - 1 };
+        let (value, draws) = round.discard_value(&kept, &discard, &removed, discards_after);
+
+        Ok(DiscardOption {
+            discard,
+            kept,
+            distribution: OutcomeDistribution::from_probabilities(value.probabilities, draws),
+            expected_score: value.expected_score as f32,
+            samples: None,
+        })
+    })?;
+
+    // Best expected score first, preferring to throw away fewer cards when tied
+    options.sort_by(|a, b| {
+        b.expected_score
+            .total_cmp(&a.expected_score)
+            .then(a.discard.len().cmp(&b.discard.len()))
+    });
+    Ok(options)
+}
+
+/// `config` cut down so that sampling a plan from `hand` scores at most `work_limit` hands.
+/// Later discards are compared over fewer draws before every option is scored over fewer
+/// samples.
+pub fn sampled_plan_config(
+    hand: &[CardData],
+    discards_remaining: u8,
+    rules: &RuleSet,
+    config: &MonteCarloConfig,
+    work_limit: u64,
+) -> MonteCarloConfig {
+    let masks = if discards_remaining == 0 {
+        1.0
+    } else {
+        discard_masks(hand, rules).count() as f64
+    };
+    let later = discards_remaining.saturating_sub(1);
+    let mut inner_samples = config.inner_samples;
+    while inner_samples > 1 && masks * MIN_SAMPLES as f64 * sampled_work(masks, later, inner_samples) > work_limit as f64 {
+        inner_samples /= 2;
+    }
+    let samples = work_limit as f64 / (masks * sampled_work(masks, later, inner_samples));
+    MonteCarloConfig {
+        max_samples: config.max_samples.min((samples as u64).max(1)),
+        inner_samples,
+        ..config.clone()
+    }
+}
+
+/// Roughly how many hands solving a plan exactly scores, for a hand of `hand_size` cards
+/// with `left` cards still to draw from. With no discards left this is the work of
+/// [`crate::exact::enumerate_draws`], and with one that of [`crate::optimizer::optimize_discard`].
+pub fn exact_work(hand_size: usize, left: usize, discards_remaining: u8, rules: &RuleSet) -> f64 {
+    // A short hand draws up to full whatever is thrown away
+    let top_up = rules.hand_size.saturating_sub(hand_size).min(left);
+    let mut work = choose(left, top_up);
+    if discards_remaining == 0 {
+        return work;
+    }
+    for size in 1..=hand_size.min(rules.max_discard_size).min(left - top_up) {
+        let draws = choose(hand_size, size) * choose(left, size + top_up);
+        work += draws * exact_work(rules.hand_size, left - size - top_up, discards_remaining - 1, rules);
+    }
+    work
+}
+
+/// Hands one sample of a sampled plan scores when `discards_remaining` discards follow
+/// the first, with `masks` options compared over `inner_samples` draws at every discard.
+fn sampled_work(masks: f64, discards_remaining: u8, inner_samples: u32) -> f64 {
+    match discards_remaining {
+        0 => 1.0,
+        _ => 1.0 + (masks - 1.0) * inner_samples as f64 + sampled_work(masks, discards_remaining - 1, inner_samples),
+    }
+}
+
+/// Number of ways of choosing `k` of `n` items, as a float so it never overflows.
+fn choose(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// The round played so far, which the joker scores the finished hand against.
+#[derive(Clone)]
+struct Round<'a> {
+    joker: Option<Joker>,
+    rules: &'a RuleSet,
+    deck: &'a Deck,
+    starting_hand: Vec<CardData>,
+    stages: Vec<DiscardStage>,
+}
+
+impl Round<'_> {
+    /// Value of locking in `hand` as it is.
+    fn stand(&self, hand: &[CardData]) -> PlayValue {
+        let evaluation = evaluate_hand_with(hand, self.rules);
+        let round = RoundContext {
+            starting_hand: &self.starting_hand,
+            stages: &self.stages,
+            final_hand: hand,
+        };
+        let mut probabilities = [0.0; HandRank::COUNT];
+        probabilities[evaluation.rank.index()] = 1.0;
+        PlayValue {
+            expected_score: score_round(&evaluation, &round, self.joker, self.rules).total as f64,
+            probabilities,
+        }
+    }
+
+    /// Best value reachable from `hand` with `discards_remaining` discards still to use.
+    fn best_play(&mut self, hand: &[CardData], removed: &[CardData], discards_remaining: u8) -> PlayValue {
+        let mut best = self.stand(hand);
+        if discards_remaining == 0 {
+            return best;
+        }
+
+        for mask in discard_masks(hand, self.rules).skip(1) {
+            let discard = subset(hand, mask);
+            let kept = subset(hand, !mask);
+            let mut removed = removed.to_vec();
+            removed.extend_from_slice(&discard);
+
+            let (value, _) = self.discard_value(&kept, &discard, &removed, discards_remaining - 1);
+            if value.expected_score > best.expected_score {
+                best = value;
+            }
+        }
+        best
+    }
+
+    /// Averages the best play over every way of drawing `kept` back up to the hand size
+    /// once `discard` is thrown away. `removed` holds every card thrown away so far,
+    /// `discard` included.
+    fn discard_value(
+        &mut self,
+        kept: &[CardData],
+        discard: &[CardData],
+        removed: &[CardData],
+        discards_remaining: u8,
+    ) -> (PlayValue, u64) {
+        let mut unavailable = kept.to_vec();
+        unavailable.extend_from_slice(removed);
+        let available = self.deck.remaining(&unavailable);
+
+        let mut total = PlayValue {
+            expected_score: 0.0,
+            probabilities: [0.0; HandRank::COUNT],
+        };
+        let mut draws = 0u64;
+        let mut hand = kept.to_vec();
+        // Topping up without a discard is not a discard stage, as in the exact engine
+        let staged = !discard.is_empty();
+        if staged {
+            self.stages.push(DiscardStage {
+                discarded: discard.to_vec(),
+                drawn: Vec::new(),
+            });
+        }
+        let draw_count = self.rules.hand_size.saturating_sub(kept.len());
+        available.for_each_draw(draw_count, |drawn, ways| {
+            hand.truncate(kept.len());
+            hand.extend_from_slice(drawn);
+            if staged && let Some(stage) = self.stages.last_mut() {
+                stage.drawn.clear();
+                stage.drawn.extend_from_slice(drawn);
+            }
+
+            let value = self.best_play(&hand, removed, discards_remaining);
+            total.expected_score += value.expected_score * ways as f64;
+            for (sum, probability) in total.probabilities.iter_mut().zip(value.probabilities) {
+                *sum += probability * ways as f64;
+            }
+            draws += ways;
+        });
+        if staged {
+            self.stages.pop();
+        }
+
+        if draws > 0 {
+            total.expected_score /= draws as f64;
+            for probability in total.probabilities.iter_mut() {
+                *probability /= draws as f64;
+            }
+        }
+        (total, draws)
+    }
+}
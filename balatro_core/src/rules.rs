@@ -0,0 +1,281 @@
+use crate::card_set::FACES;
+use crate::error::PredictorError;
+use crate::faces::CardFace;
+use crate::hand_rank::HandRank;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+/// The parameters of a variant of the game.
+///
+/// Files only need to list what differs from the DST card game, for example:
+///
+/// ```toml
+/// hand_size = 6
+/// discards = 3
+///
+/// [mult]
+/// Flush = 8
+///
+/// [base_chips]
+/// Flush = 35
+///
+/// [chips]
+/// Ace = 15
+/// ```
+///
+/// Rule sets read this way are checked with [`RuleSet::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedRuleSet"))]
+pub struct RuleSet {
+    /// Cards in a finished hand.
+    pub hand_size: usize,
+    /// Discards allowed in a round.
+    pub discards: u8,
+    /// Most cards that can be thrown away in one discard.
+    pub max_discard_size: usize,
+    /// Mult of every hand type.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_by_rank"))]
+    pub mult: [u32; HandRank::COUNT],
+    /// Chips every hand type starts with before its cards score.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_by_rank"))]
+    pub base_chips: [u32; HandRank::COUNT],
+    /// Chips of every face, indexed by the face.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_chips"))]
+    pub chips: [u32; FACES.len()],
+    /// Whether an Ace can play low in A-2-3-4-5.
+    pub ace_low: bool,
+}
+
+/// The fields of a [`RuleSet`] as read, before they are validated.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(default)]
+struct UncheckedRuleSet {
+    hand_size: usize,
+    discards: u8,
+    max_discard_size: usize,
+    #[serde(deserialize_with = "deserialize_mult")]
+    mult: [u32; HandRank::COUNT],
+    #[serde(deserialize_with = "deserialize_base_chips")]
+    base_chips: [u32; HandRank::COUNT],
+    #[serde(deserialize_with = "deserialize_chips")]
+    chips: [u32; FACES.len()],
+    ace_low: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Default for UncheckedRuleSet {
+    fn default() -> Self {
+        let RuleSet {
+            hand_size,
+            discards,
+            max_discard_size,
+            mult,
+            base_chips,
+            chips,
+            ace_low,
+        } = RuleSet::DST;
+        UncheckedRuleSet {
+            hand_size,
+            discards,
+            max_discard_size,
+            mult,
+            base_chips,
+            chips,
+            ace_low,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedRuleSet> for RuleSet {
+    type Error = PredictorError;
+
+    fn try_from(unchecked: UncheckedRuleSet) -> Result<Self, Self::Error> {
+        let rules = RuleSet {
+            hand_size: unchecked.hand_size,
+            discards: unchecked.discards,
+            max_discard_size: unchecked.max_discard_size,
+            mult: unchecked.mult,
+            base_chips: unchecked.base_chips,
+            chips: unchecked.chips,
+            ace_low: unchecked.ace_low,
+        };
+        rules.validate()?;
+        Ok(rules)
+    }
+}
+
+impl RuleSet {
+    /// The "Don't Starve Together" card game from `game_rules.md`.
+    pub const DST: RuleSet = RuleSet {
+        hand_size: 5,
+        discards: 2,
+        max_discard_size: 5,
+        mult: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+        base_chips: [0; HandRank::COUNT],
+        chips: [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 11],
+        ace_low: true,
+    };
+
+    /// Base values of Balatro: eight cards in hand, three discards, face cards worth 10 and
+    /// the level one chips and Mult of every hand type. A royal flush scores as the straight
+    /// flush it is.
+    pub const BALATRO: RuleSet = RuleSet {
+        hand_size: 8,
+        discards: 3,
+        max_discard_size: 5,
+        mult: [1, 2, 2, 3, 4, 4, 4, 7, 8, 8],
+        base_chips: [5, 10, 20, 30, 30, 35, 40, 60, 100, 100],
+        chips: [2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 10, 10, 11],
+        ace_low: true,
+    };
+
+    /// Most cards a hand can hold, since every discard from it is enumerated as a bit mask.
+    pub const MAX_HAND_SIZE: usize = 16;
+
+    /// Every preset by name.
+    pub const PRESETS: [(&'static str, RuleSet); 2] = [("dst", RuleSet::DST), ("balatro", RuleSet::BALATRO)];
+
+    /// The preset called `name`, ignoring case.
+    pub fn preset(name: &str) -> Option<RuleSet> {
+        RuleSet::PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, rules)| rules.clone())
+    }
+
+    /// Reads a rule set from a `.toml` or `.json` file and validates it.
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<RuleSet> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(toml::from_str(&text)?),
+            Some("json") => Ok(serde_json::from_str(&text)?),
+            _ => Err(anyhow::anyhow!(
+                "Cannot read rules from {}, expected a .toml or .json file",
+                path.display()
+            )),
+        }
+    }
+
+    /// Fails when the rules cannot be played: a hand of no cards or more than
+    /// [`RuleSet::MAX_HAND_SIZE`], or discards bigger than the hand.
+    pub fn validate(&self) -> Result<(), PredictorError> {
+        if !(1..=RuleSet::MAX_HAND_SIZE).contains(&self.hand_size) {
+            return Err(PredictorError::InvalidRules(format!(
+                "hand_size must be between 1 and {}, not {}",
+                RuleSet::MAX_HAND_SIZE,
+                self.hand_size
+            )));
+        }
+        if self.max_discard_size > self.hand_size {
+            return Err(PredictorError::InvalidRules(format!(
+                "max_discard_size {} is bigger than the hand_size {}",
+                self.max_discard_size, self.hand_size
+            )));
+        }
+        Ok(())
+    }
+
+    pub const fn mult(&self, rank: HandRank) -> u32 {
+        self.mult[rank.index()]
+    }
+
+    pub const fn base_chips(&self, rank: HandRank) -> u32 {
+        self.base_chips[rank.index()]
+    }
+
+    pub const fn chips(&self, face: CardFace) -> u32 {
+        self.chips[face as usize]
+    }
+
+    /// Whether hands are ranked and scored as in [`RuleSet::DST`], which the lookup tables assume.
+    pub fn scores_like_dst(&self) -> bool {
+        self.mult == RuleSet::DST.mult
+            && self.base_chips == RuleSet::DST.base_chips
+            && self.chips == RuleSet::DST.chips
+            && self.ace_low == RuleSet::DST.ace_low
+    }
+
+    /// Fails when a discard of `count` cards breaks the rules.
+    pub fn check_discard_size(&self, count: usize) -> Result<(), PredictorError> {
+        if count > self.max_discard_size {
+            return Err(PredictorError::DiscardSize {
+                max: self.max_discard_size,
+                actual: count,
+            });
+        }
+        Ok(())
+    }
+
+    /// Fails when more discards are left than the rules allow in a round.
+    pub fn check_discards_remaining(&self, discards_remaining: u8) -> Result<(), PredictorError> {
+        if discards_remaining > self.discards {
+            return Err(PredictorError::DiscardCount {
+                max: self.discards,
+                actual: discards_remaining,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::DST
+    }
+}
+
+#[cfg(feature = "serde")]
+fn serialize_by_rank<S: Serializer>(table: &[u32; HandRank::COUNT], serializer: S) -> Result<S::Ok, S::Error> {
+    let table: BTreeMap<HandRank, u32> = HandRank::ALL.iter().map(|rank| (*rank, table[rank.index()])).collect();
+    table.serialize(serializer)
+}
+
+/// Reads a table by hand type, keeping the value in `defaults` of every hand type it leaves out.
+#[cfg(feature = "serde")]
+fn deserialize_by_rank<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    defaults: [u32; HandRank::COUNT],
+) -> Result<[u32; HandRank::COUNT], D::Error> {
+    let mut table = defaults;
+    for (rank, value) in BTreeMap::<HandRank, u32>::deserialize(deserializer)? {
+        table[rank.index()] = value;
+    }
+    Ok(table)
+}
+
+/// Reads a mult table, keeping the DST mult of every hand type it leaves out.
+#[cfg(feature = "serde")]
+fn deserialize_mult<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u32; HandRank::COUNT], D::Error> {
+    deserialize_by_rank(deserializer, RuleSet::DST.mult)
+}
+
+/// Reads a base chips table, where every hand type it leaves out starts from no chips as in DST.
+#[cfg(feature = "serde")]
+fn deserialize_base_chips<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u32; HandRank::COUNT], D::Error> {
+    deserialize_by_rank(deserializer, RuleSet::DST.base_chips)
+}
+
+#[cfg(feature = "serde")]
+fn serialize_chips<S: Serializer>(chips: &[u32; FACES.len()], serializer: S) -> Result<S::Ok, S::Error> {
+    let table: BTreeMap<CardFace, u32> = FACES.iter().map(|face| (*face, chips[*face as usize])).collect();
+    table.serialize(serializer)
+}
+
+/// Reads a chips table, keeping the DST chips of every face it leaves out.
+#[cfg(feature = "serde")]
+fn deserialize_chips<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u32; FACES.len()], D::Error> {
+    let mut chips = RuleSet::DST.chips;
+    for (face, value) in BTreeMap::<CardFace, u32>::deserialize(deserializer)? {
+        chips[face as usize] = value;
+    }
+    Ok(chips)
+}
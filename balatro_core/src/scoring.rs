@@ -0,0 +1,114 @@
+use crate::card_data::CardData;
+use crate::evaluator::{HandEvaluation, SetEvaluation};
+use crate::hand_rank::HandRank;
+use crate::joker::{Joker, RoundContext};
+use crate::modifiers::{Edition, Enhancement, Seal};
+use crate::rules::RuleSet;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Points a hand is worth: the chips of its cards times the Mult of its type.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Score {
+    pub chips: u32,
+    /// Kept fractional, since ×1.5 mult does not land on a whole number.
+    pub mult: f64,
+    /// Chips × mult, rounded down.
+    pub total: u32,
+}
+
+impl Score {
+    pub fn new(chips: u32, mult: f64) -> Self {
+        Score {
+            chips,
+            mult,
+            total: (chips as f64 * mult) as u32,
+        }
+    }
+}
+
+/// Scores `cards`, which should be the cards that make up a hand of type `rank`.
+///
+/// Every card adds its value from the card values table in `game_rules.md` as chips, and
+/// the hand type decides the Mult. The rules don't say which cards count, so this assumes,
+/// as in Balatro, that only the cards making up the hand score and the rest add nothing.
+pub fn score_hand(cards: &[CardData], rank: HandRank) -> Score {
+    score_cards(cards.iter().copied(), rank, &RuleSet::DST)
+}
+
+/// Scores `cards` as a hand of type `rank`, taking the chips and Mult from `rules`. The
+/// chips start from the base chips of the hand type.
+///
+/// Card modifiers apply as each card scores, in order: a Stone card is worth 50 chips in
+/// place of its face, Bonus and Foil add chips, Mult, Lucky and Holographic add mult, Glass
+/// and Polychrome multiply it, and a Red seal scores the card twice. Only the total is
+/// rounded down, once every card has scored.
+pub fn score_cards(cards: impl IntoIterator<Item = CardData>, rank: HandRank, rules: &RuleSet) -> Score {
+    let mut chips = rules.base_chips(rank);
+    let mut mult = rules.mult(rank) as f64;
+    for card in cards {
+        for _ in 0..triggers(&card) {
+            chips += if card.is_stone() {
+                Enhancement::STONE_CHIPS
+            } else {
+                rules.chips(card.card_face)
+            };
+            match card.enhancement {
+                Some(Enhancement::Bonus) => chips += 30,
+                Some(Enhancement::Mult | Enhancement::Lucky) => mult += 4.0,
+                Some(Enhancement::Glass) => mult *= 2.0,
+                _ => {}
+            }
+            match card.edition {
+                Some(Edition::Foil) => chips += 50,
+                Some(Edition::Holographic) => mult += 10.0,
+                Some(Edition::Polychrome) => mult *= 1.5,
+                None => {}
+            }
+        }
+    }
+    Score::new(chips, mult)
+}
+
+/// Adds what the cards held in hand instead of scored do: every Steel card multiplies the
+/// mult by 1.5.
+pub fn score_held(score: Score, held: &[CardData]) -> Score {
+    let mut mult = score.mult;
+    for card in held.iter().filter(|card| card.enhancement == Some(Enhancement::Steel)) {
+        for _ in 0..triggers(card) {
+            mult *= 1.5;
+        }
+    }
+    Score::new(score.chips, mult)
+}
+
+/// Times a card's effects apply; a Red seal repeats them.
+fn triggers(card: &CardData) -> u32 {
+    if card.seal == Some(Seal::Red) { 2 } else { 1 }
+}
+
+/// Scores the cards that make up an evaluated hand; kickers are played too, so they add
+/// nothing, Steel or not. Use [`score_held`] for the cards kept back in hand.
+pub fn score_evaluation(evaluation: &HandEvaluation) -> Score {
+    score_hand(&evaluation.scoring_cards, evaluation.rank)
+}
+
+/// Scores the cards that make up a hand evaluated as a set.
+pub fn score_set(evaluation: &SetEvaluation, rules: &RuleSet) -> Score {
+    score_cards(evaluation.scoring, evaluation.rank, rules)
+}
+
+/// Scores the finished hand of `round` under `rules`, adding the selected joker's chips and mult.
+pub fn score_round(
+    evaluation: &HandEvaluation,
+    round: &RoundContext,
+    joker: Option<Joker>,
+    rules: &RuleSet,
+) -> Score {
+    let score = score_cards(evaluation.scoring_cards.iter().copied(), evaluation.rank, rules);
+    match joker {
+        Some(joker) => joker.apply(round, evaluation, score, rules),
+        None => score,
+    }
+}
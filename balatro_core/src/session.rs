@@ -0,0 +1,178 @@
+use crate::balatro_predictor::{kept_after_discard, PredictionBackend};
+use crate::calibration::PredictionOutcome;
+use crate::card_data::CardData;
+use crate::deck::Deck;
+use crate::error::{check_hand, PredictorError};
+use crate::evaluator::evaluate_hand_with;
+use crate::exact::enumerate_draws;
+use crate::joker::{DiscardStage, Joker, RoundContext};
+use crate::optimizer::DiscardOption;
+use crate::rules::RuleSet;
+use crate::scoring::{score_round, Score};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A round in progress, so predictions always draw from what is really left of the deck.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoundSession {
+    /// The cards held right now.
+    pub hand: Vec<CardData>,
+    /// The hand the round was dealt.
+    pub starting_hand: Vec<CardData>,
+    /// Every discard so far with the cards drawn for it.
+    pub stages: Vec<DiscardStage>,
+    /// Every card that has come out of the deck this round, in the order it was seen.
+    pub seen: Vec<CardData>,
+    pub discards_remaining: u8,
+    pub joker: Option<Joker>,
+    pub deck: Deck,
+    pub rules: RuleSet,
+    /// The discard options that were shown during the round.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub predictions: Vec<ShownPredictions>,
+}
+
+/// Discard options shown for the hand held after `stage` discards.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShownPredictions {
+    pub stage: usize,
+    pub options: Vec<DiscardOption>,
+}
+
+impl RoundSession {
+    /// Starts a round from the dealt `hand`, with every discard the rules allow still to play.
+    pub fn start(
+        hand: Vec<CardData>,
+        joker: Option<Joker>,
+        deck: Deck,
+        rules: RuleSet,
+    ) -> Result<Self, PredictorError> {
+        check_hand(&hand, &[], &deck, &rules)?;
+        Ok(RoundSession {
+            starting_hand: hand.clone(),
+            seen: hand.clone(),
+            hand,
+            stages: Vec::new(),
+            discards_remaining: rules.discards,
+            joker,
+            deck,
+            rules,
+            predictions: Vec::new(),
+        })
+    }
+
+    /// Every card thrown away so far, which can no longer be drawn.
+    pub fn discarded_cards(&self) -> Vec<CardData> {
+        self.stages.iter().flat_map(|stage| stage.discarded.iter().copied()).collect()
+    }
+
+    /// Throws away `cards` from the hand and adds the `drawn` cards that fill it again.
+    ///
+    /// Nothing changes when the discard breaks the rules, throws nothing away, or `drawn`
+    /// could not have come from what is left of the deck or does not fill the hand.
+    pub fn apply_discard(&mut self, cards: Vec<CardData>, drawn: Vec<CardData>) -> Result<(), PredictorError> {
+        if self.discards_remaining == 0 {
+            return Err(PredictorError::DiscardCount {
+                max: self.rules.discards,
+                actual: self.rules.discards.saturating_add(1),
+            });
+        }
+        if cards.is_empty() {
+            return Err(PredictorError::DiscardSize {
+                max: self.rules.max_discard_size,
+                actual: 0,
+            });
+        }
+        self.rules.check_discard_size(cards.len())?;
+
+        let mut hand = kept_after_discard(&self.hand, &cards)?;
+        let mut discarded_cards = self.discarded_cards();
+        discarded_cards.extend_from_slice(&cards);
+        // The hand is drawn back up to full, or for as long as the deck lasts
+        let mut unavailable = hand.clone();
+        unavailable.extend_from_slice(&discarded_cards);
+        let left = self.deck.remaining(&unavailable).len();
+        let expected = self.rules.hand_size.saturating_sub(hand.len()).min(left);
+        if drawn.len() != expected {
+            return Err(PredictorError::DrawCount {
+                expected,
+                actual: drawn.len(),
+            });
+        }
+        hand.extend_from_slice(&drawn);
+        check_hand(&hand, &discarded_cards, &self.deck, &self.rules)?;
+
+        self.hand = hand;
+        self.seen.extend_from_slice(&drawn);
+        self.stages.push(DiscardStage {
+            discarded: cards,
+            drawn,
+        });
+        self.discards_remaining -= 1;
+        Ok(())
+    }
+
+    /// Every discard that can be made from the current hand, best first, assuming the
+    /// remaining discards are played optimally afterwards.
+    ///
+    /// Either backend stays within [`crate::planner::WORK_LIMIT`] hands, so a fresh round
+    /// with every discard left is sampled rather than solved exactly.
+    pub fn predictions(&self, backend: &PredictionBackend) -> anyhow::Result<Vec<DiscardOption>> {
+        let discarded_cards = self.discarded_cards();
+        backend
+            .predictor(self.hand.clone(), self.deck.clone(), self.rules.clone())
+            .plan_discards(
+                if discarded_cards.is_empty() {
+                    None
+                } else {
+                    Some(discarded_cards)
+                },
+                self.discards_remaining,
+                self.joker,
+            )
+    }
+
+    /// Remembers `options` as the predictions shown for the current hand, replacing any
+    /// shown for it before.
+    pub fn record_predictions(&mut self, options: Vec<DiscardOption>) {
+        let stage = self.stages.len();
+        self.predictions.retain(|shown| shown.stage != stage);
+        self.predictions.push(ShownPredictions { stage, options });
+    }
+
+    /// The chance of every hand the draw for each discard so far had, next to the hand that
+    /// draw actually made.
+    ///
+    /// Each draw is judged on its own, so how the rest of the round was played does not
+    /// count for or against the prediction.
+    pub fn draw_outcomes(&self) -> anyhow::Result<Vec<PredictionOutcome>> {
+        let mut hand = self.starting_hand.clone();
+        let mut discarded_cards = Vec::new();
+        let mut outcomes = Vec::new();
+        for stage in &self.stages {
+            let kept = kept_after_discard(&hand, &stage.discarded)?;
+            // The joker only changes the score, never which hand was made
+            let outcome = enumerate_draws(&kept, &stage.discarded, &discarded_cards, &self.deck, None, &self.rules)?;
+            hand = kept;
+            hand.extend_from_slice(&stage.drawn);
+            outcomes.push(PredictionOutcome {
+                distribution: outcome.distribution(),
+                actual: evaluate_hand_with(&hand, &self.rules).rank,
+            });
+            discarded_cards.extend_from_slice(&stage.discarded);
+        }
+        Ok(outcomes)
+    }
+
+    /// Score of playing the current hand, with the joker looking at the whole round.
+    pub fn score(&self) -> Score {
+        let round = RoundContext {
+            starting_hand: &self.starting_hand,
+            stages: &self.stages,
+            final_hand: &self.hand,
+        };
+        score_round(&evaluate_hand_with(&self.hand, &self.rules), &round, self.joker, &self.rules)
+    }
+}
@@ -0,0 +1,82 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_set::CardSet;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::DECK;
+    use balatro_core::evaluator::{evaluate_hand, evaluate_set};
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_rank_is_separate_from_value() {
+        assert_eq!(CardFace::Jack.value(), CardFace::Ace.value());
+        assert_eq!(CardFace::Jack.rank(), 11);
+        assert_eq!(CardFace::Ace.rank(), 14);
+        assert_eq!(CardFace::Ace.low_rank(), 1);
+        assert_eq!(CardFace::King.low_rank(), 13);
+
+        assert_eq!(CardFace::from_rank(1), Some(CardFace::Ace));
+        assert_eq!(CardFace::from_rank(11), Some(CardFace::Jack));
+        assert_eq!(CardFace::from_rank(14), Some(CardFace::Ace));
+        assert_eq!(CardFace::from_rank(15), None);
+
+        // Every suit of the deck runs from Two up to an Ace playing high
+        let ranks: Vec<u8> = DECK[..13].iter().map(|card| card.card_face.rank()).collect();
+        assert_eq!(ranks, (2..=14).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_jack_and_ace_do_not_collide() {
+        // A Jack cannot play low in place of an Ace
+        let jack_low = [
+            CardData::new(CardFace::Jack, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Three, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Diamonds),
+            CardData::new(CardFace::Five, CardSuit::Clubs),
+        ];
+        assert_eq!(evaluate_hand(&jack_low).rank, HandRank::HighCard);
+
+        // Ten to Ace of one suit is royal, Nine to King is not
+        let royal = [CardFace::Ten, CardFace::Jack, CardFace::Queen, CardFace::King, CardFace::Ace]
+            .map(|face| CardData::new(face, CardSuit::Hearts));
+        assert_eq!(evaluate_hand(&royal).rank, HandRank::RoyalFlush);
+        let king_high = [CardFace::Nine, CardFace::Ten, CardFace::Jack, CardFace::Queen, CardFace::King]
+            .map(|face| CardData::new(face, CardSuit::Hearts));
+        assert_eq!(evaluate_hand(&king_high).rank, HandRank::StraightFlush);
+
+        let wheel = [CardFace::Ace, CardFace::Two, CardFace::Three, CardFace::Four, CardFace::Five]
+            .map(|face| CardData::new(face, CardSuit::Spades));
+        let evaluation = evaluate_hand(&wheel);
+        assert_eq!(evaluation.rank, HandRank::StraightFlush);
+        assert_eq!(evaluation.compare_strength(&evaluate_hand(&king_high)), Ordering::Less);
+    }
+
+    #[test]
+    fn test_straights_follow_rank() {
+        let suits = [CardSuit::Clubs, CardSuit::Hearts, CardSuit::Spades, CardSuit::Diamonds, CardSuit::Clubs];
+        let hand = |faces: [CardFace; 5]| -> Vec<CardData> {
+            faces.iter().zip(suits).map(|(&face, suit)| CardData::new(face, suit)).collect()
+        };
+        let rank = |cards: &[CardData]| {
+            let rank = evaluate_hand(cards).rank;
+            assert_eq!(evaluate_set(CardSet::from(cards)).rank, rank);
+            rank
+        };
+
+        // Broadway, where the Ace plays high above the King
+        let broadway = hand([CardFace::Ten, CardFace::Jack, CardFace::Queen, CardFace::King, CardFace::Ace]);
+        assert_eq!(rank(&broadway), HandRank::Straight);
+
+        // The Ace is worth as many chips as a Jack but cannot take its place in a straight
+        let ace_for_jack = hand([CardFace::Seven, CardFace::Eight, CardFace::Nine, CardFace::Ten, CardFace::Ace]);
+        assert_eq!(rank(&ace_for_jack), HandRank::HighCard);
+        let jack = hand([CardFace::Seven, CardFace::Eight, CardFace::Nine, CardFace::Ten, CardFace::Jack]);
+        assert_eq!(rank(&jack), HandRank::Straight);
+
+        // Only the Ace wraps around, so a straight cannot run from the King to the Two
+        let wrap = hand([CardFace::Queen, CardFace::King, CardFace::Ace, CardFace::Two, CardFace::Three]);
+        assert_eq!(rank(&wrap), HandRank::HighCard);
+    }
+}
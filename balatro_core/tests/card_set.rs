@@ -0,0 +1,91 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_set::CardSet;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::DECK;
+    use balatro_core::evaluator::{evaluate_hand, evaluate_set};
+    use balatro_core::exact::for_each_combination;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_every_card_has_its_own_index() {
+        let deck: CardSet = DECK.iter().collect();
+        assert_eq!(deck, CardSet::FULL);
+        for card in DECK {
+            assert_eq!(CardSet::card(CardSet::index(&card)), card);
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut hand = CardSet::EMPTY;
+        assert!(hand.insert(&CardData::new(CardFace::King, CardSuit::Spades)));
+        assert!(hand.insert(&CardData::new(CardFace::King, CardSuit::Hearts)));
+        assert!(!hand.insert(&CardData::new(CardFace::King, CardSuit::Hearts)));
+        hand |= CardSet::from(CardData::new(CardFace::Two, CardSuit::Hearts));
+
+        assert_eq!(hand.len(), 3);
+        assert_eq!(hand.count(CardFace::King), 2);
+        assert_eq!((hand & CardSet::suit(CardSuit::Hearts)).len(), 2);
+        assert_eq!(hand & CardSet::rank(CardFace::King), hand - CardSet::rank(CardFace::Two));
+        assert_eq!(hand.ranks(), 1 << CardFace::King as u8 | 1 << CardFace::Two as u8);
+        assert_eq!(hand.suit_ranks(CardSuit::Spades), 1 << CardFace::King as u8);
+        assert_eq!((!hand).len(), 49);
+        assert!(hand.is_subset(CardSet::FULL));
+
+        assert!(hand.remove(&CardData::new(CardFace::Two, CardSuit::Hearts)));
+        assert_eq!(hand, CardSet::rank(CardFace::King) & CardSet::from(&[
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+        ][..]));
+    }
+
+    #[test]
+    fn test_ace_high_and_low_straights() {
+        let broadway = [
+            CardData::new(CardFace::Ten, CardSuit::Clubs),
+            CardData::new(CardFace::Jack, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Diamonds),
+            CardData::new(CardFace::Ace, CardSuit::Clubs),
+        ];
+        assert_eq!(evaluate_hand(&broadway).rank, HandRank::Straight);
+
+        let wheel = [
+            CardData::new(CardFace::Ace, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Three, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Diamonds),
+            CardData::new(CardFace::Five, CardSuit::Clubs),
+        ];
+        let evaluation = evaluate_hand(&wheel);
+        assert_eq!(evaluation.rank, HandRank::Straight);
+        assert_eq!(evaluation.scoring_cards[0].card_face, CardFace::Five);
+        assert_eq!(evaluation.scoring_cards[4].card_face, CardFace::Ace);
+        assert_eq!(evaluation.compare_strength(&evaluate_hand(&broadway)), Ordering::Less);
+
+        // An Ace cannot stand in for the Jack
+        let gap = [
+            CardData::new(CardFace::Eight, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Ten, CardSuit::Spades),
+            CardData::new(CardFace::Ace, CardSuit::Diamonds),
+            CardData::new(CardFace::Queen, CardSuit::Clubs),
+        ];
+        assert_eq!(evaluate_hand(&gap).rank, HandRank::HighCard);
+    }
+
+    #[test]
+    fn test_every_five_card_hand() {
+        let mut counts = [0u32; HandRank::COUNT];
+        for_each_combination(&DECK, 5, |hand| {
+            counts[evaluate_set(CardSet::from(hand)).rank.index()] += 1;
+        });
+        assert_eq!(
+            counts,
+            [1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 36, 4]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::{Deck, DeckChange};
+    use balatro_core::evaluator::evaluate_hand;
+    use balatro_core::exact::{enumerate_draws, for_each_combination};
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::rules::RuleSet;
+    use balatro_core::scoring::score_evaluation;
+
+    #[test]
+    fn test_deck_builder() {
+        let deck = balatro_core::deck::DECK;
+        println!("Deck: {:?}", deck);
+        assert_eq!(deck.len(), 52);
+    }
+
+    #[test]
+    fn test_deck_changes() {
+        let standard = Deck::standard();
+        assert_eq!(standard.len(), 52);
+        assert!(standard.fits_card_set());
+
+        let ace_of_spades = CardData::new(CardFace::Ace, CardSuit::Spades);
+        let changes: Vec<DeckChange> = ["remove 2", "remove h", "add 3 As"]
+            .iter()
+            .map(|text| text.parse().unwrap())
+            .collect();
+        assert_eq!(changes[0], DeckChange::RemoveFace { face: CardFace::Two });
+        assert_eq!(changes[1], DeckChange::RemoveSuit { suit: CardSuit::Hearts });
+        assert_eq!(
+            changes[2],
+            DeckChange::AddCard {
+                card: ace_of_spades,
+                copies: 3
+            }
+        );
+        assert!("shuffle".parse::<DeckChange>().is_err());
+
+        let deck = Deck::from_changes(&changes);
+        // 48 without the twos, less the 12 other hearts, plus three aces
+        assert_eq!(deck.len(), 48 - 12 + 3);
+        assert_eq!(deck.count(&ace_of_spades), 4);
+        assert_eq!(deck.count(&CardData::new(CardFace::Two, CardSuit::Spades)), 0);
+        assert!(!deck.fits_card_set());
+
+        // Serialized as one entry per copy
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&deck).unwrap();
+            assert_eq!(serde_json::from_str::<Deck>(&json).unwrap(), deck);
+            let cards: Vec<CardData> = serde_json::from_str(&json).unwrap();
+            assert_eq!(cards.len(), deck.len());
+        }
+
+        assert!(deck.without(&[CardData::new(CardFace::Two, CardSuit::Clubs)]).is_err());
+        assert_eq!(deck.without(&[ace_of_spades]).unwrap().count(&ace_of_spades), 3);
+    }
+
+    #[test]
+    fn test_duplicate_hands() {
+        let ace_of_spades = CardData::new(CardFace::Ace, CardSuit::Spades);
+        let full_house = [
+            ace_of_spades,
+            ace_of_spades,
+            ace_of_spades,
+            CardData::new(CardFace::King, CardSuit::Clubs),
+            CardData::new(CardFace::King, CardSuit::Diamonds),
+        ];
+        let evaluation = evaluate_hand(&full_house);
+        assert_eq!(evaluation.rank, HandRank::FullHouse);
+        assert_eq!(evaluation.scoring_cards, full_house.to_vec());
+
+        let flush = [
+            ace_of_spades,
+            ace_of_spades,
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Spades),
+            CardData::new(CardFace::Two, CardSuit::Spades),
+        ];
+        assert_eq!(evaluate_hand(&flush).rank, HandRank::Flush);
+        assert_eq!(evaluate_hand(&[ace_of_spades; 5]).rank, HandRank::FourOfAKind);
+    }
+
+    #[test]
+    fn test_duplicate_card_odds() {
+        let kept = [
+            CardData::new(CardFace::Ace, CardSuit::Spades),
+            CardData::new(CardFace::Ace, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Clubs),
+            CardData::new(CardFace::Queen, CardSuit::Diamonds),
+        ];
+
+        // Two of the 48 cards left in a standard deck make three aces
+        let outcome = enumerate_draws(&kept, &[], &[], &Deck::standard(), None, &RuleSet::DST).unwrap();
+        assert_eq!(outcome.total(), 48);
+        assert_eq!(outcome.count(HandRank::ThreeOfAKind), 2);
+
+        // With two extra Ace of Spades it is four of the 50
+        let deck = Deck::from_changes(&[DeckChange::AddCard {
+            card: CardData::new(CardFace::Ace, CardSuit::Spades),
+            copies: 2,
+        }]);
+        let outcome = enumerate_draws(&kept, &[], &[], &deck, None, &RuleSet::DST).unwrap();
+        assert_eq!(outcome.total(), 50);
+        assert_eq!(outcome.count(HandRank::ThreeOfAKind), 4);
+    }
+
+    #[test]
+    fn test_duplicate_draws_match_every_copy() {
+        let deck = Deck::from_changes(&[
+            "remove c".parse().unwrap(),
+            "add 2 Kh".parse().unwrap(),
+            "add 3 Ks".parse().unwrap(),
+            "add 5d".parse().unwrap(),
+        ]);
+        let kept = [
+            CardData::new(CardFace::King, CardSuit::Hearts),
+            CardData::new(CardFace::Five, CardSuit::Hearts),
+            CardData::new(CardFace::Six, CardSuit::Diamonds),
+        ];
+        let outcome = enumerate_draws(&kept, &[], &[], &deck, None, &RuleSet::DST).unwrap();
+
+        // Treat every copy as its own card and go through every pair of them
+        let copies = deck.without(&kept).unwrap().cards();
+        let mut counts = [0u64; HandRank::COUNT];
+        let mut score_sum = 0u64;
+        let mut total = 0u64;
+        for_each_combination(&copies, 2, |drawn| {
+            let mut hand = kept.to_vec();
+            hand.extend_from_slice(drawn);
+            let evaluation = evaluate_hand(&hand);
+            counts[evaluation.rank.index()] += 1;
+            score_sum += score_evaluation(&evaluation).total as u64;
+            total += 1;
+        });
+
+        assert_eq!(outcome.total(), total);
+        for rank in HandRank::ALL {
+            assert_eq!(outcome.count(rank), counts[rank.index()], "{:?}", rank);
+        }
+        assert!((outcome.expected_score() - score_sum as f64 / total as f64).abs() < 1e-9);
+    }
+}
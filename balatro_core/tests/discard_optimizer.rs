@@ -0,0 +1,36 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::DECK;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+
+    #[test]
+    fn test_ranks_every_subset() {
+        let mut hand = vec![
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Clubs),
+            CardData::new(CardFace::Four, CardSuit::Diamonds),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+        ];
+        // Leave only the hearts and diamonds in the deck to keep the enumeration small
+        let discarded: Vec<CardData> = DECK
+            .iter()
+            .filter(|c| (c.card_suit == CardSuit::Spades || c.card_suit == CardSuit::Clubs) && !hand.contains(c))
+            .cloned()
+            .collect();
+
+        let options = hand.optimize_discard(Some(discarded), None).unwrap();
+        assert_eq!(options.len(), 32);
+        for pair in options.windows(2) {
+            assert!(pair[0].expected_score >= pair[1].expected_score);
+        }
+
+        // Throwing away the two loose cards never breaks up the three kings
+        let best = &options[0];
+        assert_eq!(best.discard.len(), 2);
+        assert!(best.kept.iter().all(|c| c.card_face == CardFace::King));
+        assert_eq!(best.distribution.probability(HandRank::Pair), 0.0);
+    }
+}
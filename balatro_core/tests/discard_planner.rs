@@ -0,0 +1,121 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::{Deck, DECK};
+    use balatro_core::faces::CardFace;
+    use balatro_core::notation::Hand;
+    use balatro_core::monte_carlo::MonteCarloConfig;
+    use balatro_core::planner::{plan_discards, plan_discards_within, sampled_plan_config, WORK_LIMIT};
+    use balatro_core::rules::RuleSet;
+
+    /// Everything except the hand and `left` is already out of the deck.
+    fn deck_leaving(hand: &[CardData], left: &[CardData]) -> Vec<CardData> {
+        DECK.iter()
+            .filter(|c| !hand.contains(c) && !left.contains(c))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_second_discard_adds_value() {
+        let mut hand = vec![
+            CardData::new(CardFace::Queen, CardSuit::Spades),
+            CardData::new(CardFace::Queen, CardSuit::Hearts),
+            CardData::new(CardFace::Eight, CardSuit::Clubs),
+            CardData::new(CardFace::Five, CardSuit::Diamonds),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+        ];
+        let left = [
+            CardData::new(CardFace::Queen, CardSuit::Clubs),
+            CardData::new(CardFace::Queen, CardSuit::Diamonds),
+            CardData::new(CardFace::Eight, CardSuit::Hearts),
+            CardData::new(CardFace::Seven, CardSuit::Spades),
+            CardData::new(CardFace::Three, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Spades),
+            CardData::new(CardFace::Nine, CardSuit::Diamonds),
+        ];
+        let discarded = deck_leaving(&hand, &left);
+
+        let locked = hand.plan_discards(Some(discarded.clone()), 0, None).unwrap();
+        assert_eq!(locked.len(), 1);
+        assert!(locked[0].discard.is_empty());
+
+        // With a single discard the planner is the one stage optimizer
+        let one = hand.plan_discards(Some(discarded.clone()), 1, None).unwrap();
+        let optimized = hand.optimize_discard(Some(discarded.clone()), None).unwrap();
+        assert_eq!(one.len(), 32);
+        assert!((one[0].expected_score - optimized[0].expected_score).abs() < 1e-5);
+
+        // A second discard can only help, whatever is thrown away first
+        let two = hand.plan_discards(Some(discarded), 2, None).unwrap();
+        assert!(two[0].expected_score > one[0].expected_score);
+        for option in &two {
+            let single = one.iter().find(|o| o.discard == option.discard).unwrap();
+            assert!(option.expected_score >= single.expected_score - 1e-5);
+
+            let total: f32 = option.distribution.probabilities.values().sum();
+            assert!((total - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_plan_over_the_limit_is_sampled() {
+        // Two discards against a full deck are far too many draws to enumerate
+        let hand: Vec<CardData> = "2h 7c 9d Ks 4s".parse::<Hand>().unwrap().into();
+        let plan = |limit| plan_discards_within(&hand, &[], 2, &Deck::standard(), None, &RuleSet::DST, limit).unwrap();
+        let options = plan(100_000);
+        assert_eq!(options.len(), 32);
+        assert!(options.windows(2).all(|pair| pair[0].expected_score >= pair[1].expected_score));
+        for option in &options {
+            // Sampled options say so
+            assert_eq!(option.samples, Some(option.distribution.draws));
+            assert!(option.distribution.draws > 0 && option.distribution.draws <= 100_000);
+            let total: f32 = option.distribution.probabilities.values().sum();
+            assert!((total - 1.0).abs() < 1e-4);
+        }
+
+        // Sampled plans use a fixed seed, so they give the same answer every time
+        let again = plan(100_000);
+        assert!(options.iter().zip(&again).all(|(a, b)| a.discard == b.discard && a.expected_score == b.expected_score));
+    }
+
+    #[test]
+    fn test_predictions_stay_within_the_limit() {
+        let hand: Vec<CardData> = "2h 7c 9d Ks 4s".parse::<Hand>().unwrap().into();
+        let config = MonteCarloConfig::default();
+
+        // A fresh round samples far fewer first draws than the config asks for
+        let fresh = sampled_plan_config(&hand, 2, &RuleSet::DST, &config, WORK_LIMIT);
+        assert!(fresh.max_samples < config.max_samples);
+        assert!(fresh.inner_samples <= config.inner_samples);
+        let work = 32 * fresh.max_samples * (2 + 31 * fresh.inner_samples as u64);
+        assert!(work <= WORK_LIMIT);
+
+        // A single discard already fits
+        assert_eq!(sampled_plan_config(&hand, 1, &RuleSet::DST, &config, WORK_LIMIT), config);
+    }
+
+    #[test]
+    fn test_short_hand_plans_agree() {
+        // Four cards draw one more before anything is played, however many discards are left
+        let hand: Vec<CardData> = "Ah Ad 3c 7s".parse::<Hand>().unwrap().into();
+        let rules = RuleSet {
+            max_discard_size: 1,
+            ..RuleSet::DST
+        };
+        let plan = |discards| plan_discards(&hand, &[], discards, &Deck::standard(), None, &rules).unwrap();
+        let (none, one, two) = (plan(0), plan(1), plan(2));
+
+        let exact = hand.clone().calculate_expected_score(None, None, None).unwrap();
+        assert!((none[0].expected_score - exact).abs() < 1e-3);
+        assert!(none.iter().chain(&one).chain(&two).all(|option| option.samples.is_none()));
+        for option in &one {
+            let again = two.iter().find(|other| other.discard == option.discard).unwrap();
+            assert!(again.expected_score >= option.expected_score - 1e-3, "{:?}", option.discard);
+            if option.discard.is_empty() {
+                assert!((option.expected_score - exact).abs() < 1e-3);
+                assert!((again.expected_score - exact).abs() < 1e-3);
+            }
+        }
+    }
+}
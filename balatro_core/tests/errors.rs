@@ -0,0 +1,86 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::Deck;
+    use balatro_core::error::{check_hand, PredictorError};
+    use balatro_core::exact::enumerate_draws;
+    use balatro_core::faces::CardFace;
+    use balatro_core::optimizer::optimize_discard;
+    use balatro_core::planner::plan_discards;
+    use balatro_core::rules::RuleSet;
+
+    fn error_of(result: anyhow::Result<impl std::fmt::Debug>) -> PredictorError {
+        PredictorError::from(result.unwrap_err())
+    }
+
+    #[test]
+    fn test_duplicate_card() {
+        let ace = CardData::new(CardFace::Ace, CardSuit::Spades);
+        let hand = vec![ace, ace, CardData::new(CardFace::Two, CardSuit::Clubs)];
+        let deck = Deck::standard();
+
+        let error = error_of(optimize_discard(&hand, &[], &deck, None, &RuleSet::DST));
+        assert_eq!(error, PredictorError::DuplicateCard { cards: vec![ace] });
+
+        // A deck with a second copy can deal the same card twice
+        let mut doubled = Deck::standard();
+        doubled.add(&ace, 1);
+        assert!(check_hand(&hand, &[], &doubled, &RuleSet::DST).is_ok());
+    }
+
+    #[test]
+    fn test_discarded_in_hand() {
+        let king = CardData::new(CardFace::King, CardSuit::Hearts);
+        let hand = vec![king, CardData::new(CardFace::Two, CardSuit::Clubs)];
+        let discarded = vec![king, CardData::new(CardFace::Five, CardSuit::Spades)];
+
+        let error = error_of(enumerate_draws(&hand, &[], &discarded, &Deck::standard(), None, &RuleSet::DST));
+        assert_eq!(error, PredictorError::DiscardedInHand { cards: vec![king] });
+    }
+
+    #[test]
+    fn test_missing_from_deck() {
+        let two = CardData::new(CardFace::Two, CardSuit::Clubs);
+        let three = CardData::new(CardFace::Three, CardSuit::Clubs);
+        let deck = Deck::from_changes(&["remove c".parse().unwrap()]);
+
+        let error = error_of(plan_discards(&[two, three], &[], 1, &deck, None, &RuleSet::DST));
+        assert_eq!(error, PredictorError::MissingFromDeck { cards: vec![two, three] });
+    }
+
+    #[test]
+    fn test_sizes_and_counts() {
+        let hand: Vec<CardData> = [CardFace::Two, CardFace::Three, CardFace::Four, CardFace::Five, CardFace::Six, CardFace::Seven]
+            .map(|face| CardData::new(face, CardSuit::Diamonds))
+            .to_vec();
+        let deck = Deck::standard();
+
+        let error = error_of(optimize_discard(&hand, &[], &deck, None, &RuleSet::DST));
+        assert_eq!(error, PredictorError::HandSize { expected: 5, actual: 6 });
+
+        let error = error_of(plan_discards(&hand[..5], &[], 3, &deck, None, &RuleSet::DST));
+        assert_eq!(error, PredictorError::DiscardCount { max: 2, actual: 3 });
+
+        let rules = RuleSet {
+            max_discard_size: 2,
+            ..RuleSet::DST
+        };
+        let error = error_of(enumerate_draws(&hand[..2], &hand[2..5], &[], &deck, None, &rules));
+        assert_eq!(error, PredictorError::DiscardSize { max: 2, actual: 3 });
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialized_error() {
+        let ace = CardData::new(CardFace::Ace, CardSuit::Spades);
+        let value = serde_json::to_value(PredictorError::DuplicateCard { cards: vec![ace] }).unwrap();
+        assert_eq!(value["code"], "duplicate_card");
+        assert!(value["message"].as_str().unwrap().contains(&ace.to_string()));
+        assert_eq!(value["cards"], serde_json::to_value(vec![ace]).unwrap());
+
+        let value = serde_json::to_value(PredictorError::from(anyhow::anyhow!("Something else"))).unwrap();
+        assert_eq!(value["code"], "other");
+        assert_eq!(value["message"], "Something else");
+        assert_eq!(value["cards"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,60 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::Deck;
+    use balatro_core::exact::enumerate_draws;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::rules::RuleSet;
+
+    #[test]
+    fn test_distribution_sums_to_one() {
+        let kept = vec![
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            CardData::new(CardFace::King, CardSuit::Spades),
+        ];
+        let outcome = enumerate_draws(&kept, &[], &[], &Deck::standard(), None, &RuleSet::DST).unwrap();
+        assert_eq!(outcome.total(), 1176); // C(49, 2)
+
+        let total: f64 = HandRank::ALL.iter().map(|&rank| outcome.probability(rank)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_four_to_a_flush() {
+        let mut hand = vec![
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Five, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Hearts),
+        ];
+        // 9 hearts remain out of the 48 cards that can still be drawn
+        let probability = hand.calculate_flush_probability(None).unwrap();
+        assert!((probability - 9.0 / 48.0).abs() < 1e-6);
+
+        // Discarding two hearts removes them from the draw pile
+        let discarded = vec![
+            CardData::new(CardFace::Three, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Hearts),
+        ];
+        let probability = hand.calculate_flush_probability(Some(discarded)).unwrap();
+        assert!((probability - 7.0 / 46.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_complete_hand_is_certain() {
+        let mut hand = vec![
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Ten, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Clubs),
+        ];
+        assert_eq!(hand.calculate_pair_probability(None).unwrap(), 1.0);
+        assert_eq!(hand.calculate_high_card_probability(None).unwrap(), 0.0);
+
+        hand.push(CardData::new(CardFace::Two, CardSuit::Clubs));
+        assert!(hand.calculate_pair_probability(None).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::evaluator::evaluate_hand;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_hand_ranks() {
+        let full_house = [
+            CardData::new(CardFace::Ten, CardSuit::Spades),
+            CardData::new(CardFace::Six, CardSuit::Spades),
+            CardData::new(CardFace::Ten, CardSuit::Clubs),
+            CardData::new(CardFace::Six, CardSuit::Clubs),
+            CardData::new(CardFace::Ten, CardSuit::Diamonds),
+        ];
+        let evaluation = evaluate_hand(&full_house);
+        assert_eq!(evaluation.rank, HandRank::FullHouse);
+        assert_eq!(evaluation.scoring_cards[0].card_face, CardFace::Ten);
+        assert_eq!(evaluation.scoring_cards[4].card_face, CardFace::Six);
+        assert!(evaluation.kickers.is_empty());
+
+        let royal_flush = [
+            CardData::new(CardFace::Ten, CardSuit::Diamonds),
+            CardData::new(CardFace::Jack, CardSuit::Diamonds),
+            CardData::new(CardFace::Queen, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Diamonds),
+            CardData::new(CardFace::Ace, CardSuit::Diamonds),
+        ];
+        assert_eq!(evaluate_hand(&royal_flush).rank, HandRank::RoyalFlush);
+
+        let two_pair = [
+            CardData::new(CardFace::Queen, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Spades),
+            CardData::new(CardFace::Five, CardSuit::Diamonds),
+            CardData::new(CardFace::Five, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Spades),
+        ];
+        let evaluation = evaluate_hand(&two_pair);
+        assert_eq!(evaluation.rank, HandRank::TwoPair);
+        assert_eq!(evaluation.scoring_cards.len(), 4);
+        assert_eq!(evaluation.kickers, vec![CardData::new(CardFace::Two, CardSuit::Spades)]);
+    }
+
+    #[test]
+    fn test_tie_breaking() {
+        let kings = evaluate_hand(&[
+            CardData::new(CardFace::King, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Diamonds),
+            CardData::new(CardFace::Three, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Spades),
+        ]);
+        let sevens = evaluate_hand(&[
+            CardData::new(CardFace::Seven, CardSuit::Hearts),
+            CardData::new(CardFace::Seven, CardSuit::Spades),
+            CardData::new(CardFace::Ace, CardSuit::Diamonds),
+            CardData::new(CardFace::Queen, CardSuit::Clubs),
+            CardData::new(CardFace::Ten, CardSuit::Spades),
+        ]);
+        let flush = evaluate_hand(&[
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Jack, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+        ]);
+        assert_eq!(kings.compare_strength(&sevens), Ordering::Greater);
+        assert_eq!(flush.compare_strength(&kings), Ordering::Greater);
+
+        // The same faces in other suits tie without being the same evaluation
+        let king_high = evaluate_hand(&[
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Spades),
+        ]);
+        let other_suits = evaluate_hand(&[
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Diamonds),
+            CardData::new(CardFace::Four, CardSuit::Hearts),
+        ]);
+        assert_eq!(king_high.compare_strength(&other_suits), Ordering::Equal);
+        assert_ne!(king_high, other_suits);
+    }
+}
@@ -0,0 +1,148 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::evaluator::{evaluate_hand, evaluate_hand_with};
+    use balatro_core::faces::CardFace;
+    use balatro_core::joker::{DiscardStage, Joker, RoundContext};
+    use balatro_core::notation::Hand;
+    use balatro_core::rules::RuleSet;
+    use balatro_core::scoring::{score_evaluation, score_round};
+
+    fn pair_of_kings() -> Vec<CardData> {
+        vec![
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::Four, CardSuit::Spades),
+        ]
+    }
+
+    #[test]
+    fn test_held_card_jokers() {
+        let hand = pair_of_kings();
+        let evaluation = evaluate_hand(&hand);
+        let round = RoundContext {
+            starting_hand: &hand,
+            stages: &[],
+            final_hand: &hand,
+        };
+        let base = score_evaluation(&evaluation);
+        assert_eq!(score_round(&evaluation, &round, None, &RuleSet::DST), base);
+
+        // Two Kings in hand
+        let wolfgang = score_round(&evaluation, &round, Some(Joker::Wolfgang), &RuleSet::DST);
+        assert_eq!(wolfgang.chips, base.chips + 50);
+        assert_eq!(wolfgang.mult, base.mult);
+
+        // Two Spades in hand
+        let wigfrid = score_round(&evaluation, &round, Some(Joker::Wigfrid), &RuleSet::DST);
+        assert_eq!(wigfrid.chips, base.chips + 50);
+
+        // One pair and one Queen
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Wilson), &RuleSet::DST).mult, base.mult + 3.0);
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Wickerbottom), &RuleSet::DST).mult, base.mult + 1.0);
+
+        // Every suit is held
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Warly), &RuleSet::DST).mult, base.mult + 4.0);
+
+        // Wanda starts with 80 chips when nothing was thrown away
+        let wanda = score_round(&evaluation, &round, Some(Joker::Wanda), &RuleSet::DST);
+        assert_eq!(wanda.total, ((base.chips + 80) as f64 * base.mult) as u32);
+    }
+
+    #[test]
+    fn test_discard_jokers() {
+        let starting_hand = pair_of_kings();
+        let stages = [DiscardStage {
+            discarded: vec![
+                CardData::new(CardFace::Seven, CardSuit::Diamonds),
+                CardData::new(CardFace::Four, CardSuit::Spades),
+            ],
+            drawn: vec![
+                CardData::new(CardFace::Two, CardSuit::Clubs),
+                CardData::new(CardFace::Nine, CardSuit::Spades),
+            ],
+        }];
+        let final_hand = vec![
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+        ];
+        let evaluation = evaluate_hand(&final_hand);
+        let round = RoundContext {
+            starting_hand: &starting_hand,
+            stages: &stages,
+            final_hand: &final_hand,
+        };
+        let base = score_evaluation(&evaluation);
+
+        // Two cards thrown away
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Woodie), &RuleSet::DST).chips, base.chips + 14);
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Wanda), &RuleSet::DST).chips, base.chips + 65);
+
+        // A Diamond and a Spade were discarded
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Walter), &RuleSet::DST).chips, base.chips + 30);
+
+        // The Diamond was replaced by a Club, the Spade by another Spade
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Webber), &RuleSet::DST).mult, base.mult + 2.0);
+        let wendy = score_round(&evaluation, &round, Some(Joker::Wendy), &RuleSet::DST);
+        assert_eq!((wendy.chips, wendy.mult), (base.chips + 5, base.mult + 2.0));
+
+        // The Queen of Clubs and the Kings were kept; the King of Hearts is the only heart
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Wormwood), &RuleSet::DST).chips, base.chips + 15);
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Winona), &RuleSet::DST).mult, base.mult + 1.0);
+        assert_eq!(score_round(&evaluation, &round, Some(Joker::Wurt), &RuleSet::DST).chips, base.chips + 90);
+    }
+
+    #[test]
+    fn test_wes_compares_under_the_round_rules() {
+        let cards = |text: &str| -> Vec<CardData> { text.parse::<Hand>().unwrap().into() };
+        let starting_hand = cards("Ah 2c 3d 4s 5h 9c Jd Kc");
+        let stages = [DiscardStage {
+            discarded: cards("3d 4s 5h"),
+            drawn: cards("Ad 7s 8h"),
+        }];
+        let final_hand = cards("Ah 2c 9c Jd Kc Ad 7s 8h");
+        let round = RoundContext {
+            starting_hand: &starting_hand,
+            stages: &stages,
+            final_hand: &final_hand,
+        };
+        let wes = |rules: &RuleSet| {
+            let evaluation = evaluate_hand_with(&final_hand, rules);
+            let bonus = score_round(&evaluation, &round, Some(Joker::Wes), rules).chips;
+            bonus - score_round(&evaluation, &round, None, rules).chips
+        };
+
+        // The wheel the round started with beats the pair of Aces it finished with
+        assert_eq!(wes(&RuleSet::BALATRO), 30);
+        // Without a low Ace the eight cards started as nothing, so the pair is better
+        let ace_high_only = RuleSet {
+            ace_low: false,
+            ..RuleSet::BALATRO
+        };
+        assert_eq!(wes(&ace_high_only), 0);
+    }
+
+    #[test]
+    fn test_expected_score_with_joker() {
+        let mut hand = pair_of_kings();
+        let discard = vec![CardData::new(CardFace::Four, CardSuit::Spades)];
+        let plain = hand
+            .calculate_expected_score(None, Some(discard.clone()), None)
+            .unwrap();
+        let wanda = hand
+            .calculate_expected_score(None, Some(discard), Some(Joker::Wanda))
+            .unwrap();
+
+        // Wanda adds 65 chips to every hand after one discard, and every hand is at least a pair
+        assert!(wanda >= plain + 65.0 * 2.0);
+
+        let options = hand.optimize_discard(None, Some(Joker::Wanda)).unwrap();
+        assert_eq!(options.len(), 32);
+        assert!(options.windows(2).all(|pair| pair[0].expected_score >= pair[1].expected_score));
+    }
+}
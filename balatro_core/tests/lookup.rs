@@ -0,0 +1,34 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_set::CardSet;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::DECK;
+    use balatro_core::evaluator::evaluate_set;
+    use balatro_core::exact::for_each_combination;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::lookup::{lookup_five, lookup_rank};
+    use balatro_core::rules::RuleSet;
+    use balatro_core::scoring::score_set;
+
+    #[test]
+    fn test_lookup_matches_evaluator() {
+        for_each_combination(&DECK, 5, |hand| {
+            let cards = CardSet::from(hand);
+            let evaluation = evaluate_set(cards);
+            let entry = lookup_five(cards).unwrap();
+            assert_eq!(entry.rank, evaluation.rank);
+            assert_eq!(entry.score, score_set(&evaluation, &RuleSet::DST));
+        });
+    }
+
+    #[test]
+    fn test_lookup_needs_five_cards() {
+        let pair = CardSet::from(&[
+            CardData::new(CardFace::Nine, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+        ][..]);
+        assert_eq!(lookup_five(pair), None);
+        assert_eq!(lookup_rank(pair), HandRank::Pair);
+    }
+}
@@ -0,0 +1,153 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::evaluator::evaluate_hand;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::modifiers::{Edition, Enhancement, Seal};
+    use balatro_core::scoring::{score_evaluation, score_held};
+
+    fn enhanced(card_face: CardFace, card_suit: CardSuit, enhancement: Enhancement) -> CardData {
+        CardData {
+            enhancement: Some(enhancement),
+            ..CardData::new(card_face, card_suit)
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json() {
+        // Payloads from before the modifiers still read, and plain cards write the same
+        let old: CardData = serde_json::from_str(r#"{"card_suit": "Hearts", "card_face": "Ace"}"#).unwrap();
+        assert_eq!(old, CardData::new(CardFace::Ace, CardSuit::Hearts));
+        assert_eq!(
+            serde_json::to_string(&old).unwrap(),
+            r#"{"card_suit":"Hearts","card_face":"Ace"}"#
+        );
+
+        let glass: CardData = serde_json::from_str(
+            r#"{"card_suit": "Spades", "card_face": "King", "enhancement": "Glass", "edition": "Foil", "seal": "Red"}"#,
+        )
+        .unwrap();
+        assert_eq!(glass.enhancement, Some(Enhancement::Glass));
+        assert_eq!(glass.edition, Some(Edition::Foil));
+        assert_eq!(glass.seal, Some(Seal::Red));
+        let json = serde_json::to_string(&glass).unwrap();
+        assert_eq!(serde_json::from_str::<CardData>(&json).unwrap(), glass);
+    }
+
+    #[test]
+    fn test_wild_and_stone_ranking() {
+        let flush = [
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Jack, CardSuit::Hearts),
+            enhanced(CardFace::King, CardSuit::Spades, Enhancement::Wild),
+        ];
+        let evaluation = evaluate_hand(&flush);
+        assert_eq!(evaluation.rank, HandRank::Flush);
+        assert_eq!(evaluation.scoring_cards[0], flush[4]);
+
+        let straight_flush = [
+            CardData::new(CardFace::Five, CardSuit::Clubs),
+            enhanced(CardFace::Six, CardSuit::Diamonds, Enhancement::Wild),
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            enhanced(CardFace::Eight, CardSuit::Hearts, Enhancement::Wild),
+            CardData::new(CardFace::Nine, CardSuit::Clubs),
+        ];
+        assert_eq!(evaluate_hand(&straight_flush).rank, HandRank::StraightFlush);
+
+        // A Stone card has no rank, so it makes no pair, but it always scores
+        let stone = enhanced(CardFace::Ace, CardSuit::Spades, Enhancement::Stone);
+        let hand = [
+            stone,
+            CardData::new(CardFace::Ace, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Diamonds),
+            CardData::new(CardFace::Four, CardSuit::Spades),
+        ];
+        let evaluation = evaluate_hand(&hand);
+        assert_eq!(evaluation.rank, HandRank::HighCard);
+        assert_eq!(evaluation.scoring_cards, vec![hand[1], stone]);
+        assert_eq!(score_evaluation(&evaluation).chips, 11 + 50);
+    }
+
+    #[test]
+    fn test_scoring_modifiers() {
+        let seven = |modify: fn(&mut CardData)| {
+            let mut seven = CardData::new(CardFace::Seven, CardSuit::Clubs);
+            modify(&mut seven);
+            let hand = [
+                seven,
+                CardData::new(CardFace::Seven, CardSuit::Diamonds),
+                CardData::new(CardFace::King, CardSuit::Spades),
+                CardData::new(CardFace::Ten, CardSuit::Hearts),
+                CardData::new(CardFace::Four, CardSuit::Clubs),
+            ];
+            score_evaluation(&evaluate_hand(&hand))
+        };
+
+        let plain = seven(|_| {});
+        assert_eq!((plain.chips, plain.mult), (14, 2.0));
+        let bonus = seven(|card| card.enhancement = Some(Enhancement::Bonus));
+        assert_eq!((bonus.chips, bonus.mult), (44, 2.0));
+        let mult = seven(|card| card.enhancement = Some(Enhancement::Mult));
+        assert_eq!((mult.chips, mult.mult), (14, 6.0));
+        let glass = seven(|card| card.enhancement = Some(Enhancement::Glass));
+        assert_eq!((glass.chips, glass.mult, glass.total), (14, 4.0, 56));
+        let foil = seven(|card| card.edition = Some(Edition::Foil));
+        assert_eq!(foil.chips, 64);
+        let holographic = seven(|card| card.edition = Some(Edition::Holographic));
+        assert_eq!(holographic.mult, 12.0);
+        let polychrome = seven(|card| card.edition = Some(Edition::Polychrome));
+        assert_eq!(polychrome.mult, 3.0);
+
+        // A Red seal scores the card twice
+        let red = seven(|card| {
+            card.enhancement = Some(Enhancement::Glass);
+            card.seal = Some(Seal::Red);
+        });
+        assert_eq!((red.chips, red.mult), (21, 8.0));
+
+        // ×1.5 mult keeps its fraction, and only the total is rounded down
+        let red_polychrome = seven(|card| {
+            card.edition = Some(Edition::Polychrome);
+            card.seal = Some(Seal::Red);
+        });
+        assert_eq!((red_polychrome.mult, red_polychrome.total), (4.5, 94));
+
+        // Steel works from the hand, not when played, even as a kicker
+        let king = |enhancement| {
+            let hand = [
+                CardData::new(CardFace::Seven, CardSuit::Clubs),
+                CardData::new(CardFace::Seven, CardSuit::Diamonds),
+                enhanced(CardFace::King, CardSuit::Spades, enhancement),
+                CardData::new(CardFace::Ten, CardSuit::Hearts),
+                CardData::new(CardFace::Four, CardSuit::Clubs),
+            ];
+            score_evaluation(&evaluate_hand(&hand))
+        };
+        assert_eq!(king(Enhancement::Steel).mult, 2.0);
+        assert_eq!(king(Enhancement::Gold).mult, 2.0);
+        let held = [enhanced(CardFace::King, CardSuit::Spades, Enhancement::Steel)];
+        assert_eq!(score_held(king(Enhancement::Steel), &held).mult, 3.0);
+    }
+
+    #[test]
+    fn test_predicting_with_modifiers() {
+        // An enhanced card in hand stands in for its plain copy in the deck
+        let mut hand = vec![
+            enhanced(CardFace::Seven, CardSuit::Clubs, Enhancement::Glass),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Ten, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Clubs),
+        ];
+        assert_eq!(hand.calculate_expected_score(None, None, None).unwrap(), 56.0);
+
+        let discard = vec![CardData::new(CardFace::Four, CardSuit::Clubs)];
+        let expected = hand.calculate_expected_score(None, Some(discard), None).unwrap();
+        assert!(expected >= 56.0);
+    }
+}
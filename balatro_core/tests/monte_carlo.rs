@@ -0,0 +1,153 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::Deck;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::monte_carlo::{simulate, MonteCarloConfig};
+    use balatro_core::rules::RuleSet;
+
+    fn hand() -> Vec<CardData> {
+        vec![
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Five, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Queen, CardSuit::Hearts),
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+        ]
+    }
+
+    #[test]
+    fn test_sampling_matches_exact() {
+        let discard = vec![CardData::new(CardFace::Seven, CardSuit::Clubs)];
+        let config = MonteCarloConfig {
+            seed: Some(7),
+            max_samples: 20_000,
+            ..Default::default()
+        };
+        let sampled = simulate(
+            &hand(),
+            &[],
+            &discard,
+            0,
+            &Deck::standard(),
+            None,
+            &config,
+            &RuleSet::DST,
+        )
+        .unwrap();
+        let exact = hand()
+            .calculate_outcome_distribution(None, Some(discard.clone()))
+            .unwrap();
+
+        assert_eq!(sampled.samples, 20_000);
+        let flush = sampled.probabilities[&HandRank::Flush];
+        assert!(flush.lower <= flush.mean && flush.mean <= flush.upper);
+        assert!(flush.lower < exact.probability(HandRank::Flush) as f64);
+        assert!(flush.upper > exact.probability(HandRank::Flush) as f64);
+
+        // The same seed replays the same shuffles
+        let replay = simulate(
+            &hand(),
+            &[],
+            &discard,
+            0,
+            &Deck::standard(),
+            None,
+            &config,
+            &RuleSet::DST,
+        )
+        .unwrap();
+        assert_eq!(sampled, replay);
+    }
+
+    #[test]
+    fn test_short_hand_is_drawn_up_to_full() {
+        // Three cards draw two more, for either backend
+        let hand = vec![
+            CardData::new(CardFace::Ace, CardSuit::Hearts),
+            CardData::new(CardFace::Ace, CardSuit::Diamonds),
+            CardData::new(CardFace::Three, CardSuit::Clubs),
+        ];
+        let config = MonteCarloConfig {
+            seed: Some(1),
+            max_samples: 20_000,
+            ..Default::default()
+        };
+        let sampled = simulate(&hand, &[], &[], 0, &Deck::standard(), None, &config, &RuleSet::DST).unwrap();
+        let exact = hand.clone().calculate_outcome_distribution(None, None).unwrap();
+
+        assert!(exact.probability(HandRank::TwoPair) > 0.1);
+        for rank in HandRank::ALL {
+            let estimate = sampled.probabilities[&rank];
+            let probability = exact.probability(rank) as f64;
+            assert!(estimate.lower <= probability && probability <= estimate.upper, "{:?}", rank);
+        }
+        let expected_score = hand.clone().calculate_expected_score(None, None, None).unwrap() as f64;
+        assert!(sampled.expected_score.lower <= expected_score && expected_score <= sampled.expected_score.upper);
+    }
+
+    #[test]
+    fn test_stops_at_tolerance() {
+        let config = MonteCarloConfig {
+            seed: Some(1),
+            max_samples: 1_000_000,
+            tolerance: Some(0.02),
+            ..Default::default()
+        };
+        let discard = hand()[3..].to_vec();
+        let sampled = simulate(
+            &hand(),
+            &[],
+            &discard,
+            0,
+            &Deck::standard(),
+            None,
+            &config,
+            &RuleSet::DST,
+        )
+        .unwrap();
+        assert!(sampled.samples < 1_000_000);
+        for estimate in sampled.probabilities.values() {
+            assert!(estimate.half_width() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_plays_out_later_discards() {
+        let config = MonteCarloConfig {
+            seed: Some(3),
+            max_samples: 200,
+            inner_samples: 16,
+            ..Default::default()
+        };
+        let discard = vec![CardData::new(CardFace::Seven, CardSuit::Clubs)];
+        let locked = simulate(
+            &hand(),
+            &[],
+            &discard,
+            0,
+            &Deck::standard(),
+            None,
+            &config,
+            &RuleSet::DST,
+        )
+        .unwrap();
+        let two_stage = simulate(
+            &hand(),
+            &[],
+            &discard,
+            1,
+            &Deck::standard(),
+            None,
+            &config,
+            &RuleSet::DST,
+        )
+        .unwrap();
+
+        assert_eq!(two_stage.samples, 200);
+        let total: f32 = two_stage.distribution.probabilities.values().sum();
+        assert!((total - 1.0).abs() < 1e-4);
+        assert!(two_stage.expected_score.mean >= locked.expected_score.mean);
+    }
+}
@@ -0,0 +1,51 @@
+mod test {
+    use balatro_core::card_data::CardData;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::DECK;
+    use balatro_core::faces::CardFace;
+    use balatro_core::notation::{Hand, ParseCardErrorKind};
+
+    #[test]
+    fn test_parse_cards() {
+        assert_eq!("Ah".parse::<CardData>().unwrap(), CardData::new(CardFace::Ace, CardSuit::Hearts));
+        assert_eq!("10s".parse::<CardData>().unwrap(), CardData::new(CardFace::Ten, CardSuit::Spades));
+        assert_eq!("Td".parse::<CardData>().unwrap(), CardData::new(CardFace::Ten, CardSuit::Diamonds));
+        assert_eq!("K♥".parse::<CardData>().unwrap(), CardData::new(CardFace::King, CardSuit::Hearts));
+        assert_eq!("qC".parse::<CardData>().unwrap(), CardData::new(CardFace::Queen, CardSuit::Clubs));
+
+        // Every card reads back the way it is written
+        for card in DECK {
+            assert_eq!(card.to_string().parse::<CardData>().unwrap(), card);
+        }
+    }
+
+    #[test]
+    fn test_parse_hand() {
+        let hand: Hand = "As Kh 10d, 4c  4s".parse().unwrap();
+        assert_eq!(hand.len(), 5);
+        assert_eq!(hand[2], CardData::new(CardFace::Ten, CardSuit::Diamonds));
+        assert_eq!(hand.to_string(), "As Kh 10d 4c 4s");
+        assert!("".parse::<Hand>().unwrap().is_empty());
+
+        let error = "As Kh 1d 4c".parse::<Hand>().unwrap_err();
+        assert_eq!(error.kind, ParseCardErrorKind::UnknownFace);
+        assert_eq!(error.token, "1d");
+        assert_eq!(error.position, 6);
+
+        let error = "As Kx".parse::<Hand>().unwrap_err();
+        assert_eq!(error.kind, ParseCardErrorKind::UnknownSuit);
+        assert_eq!(error.token, "Kx");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize_either_form() {
+        let cards: Vec<CardData> =
+            serde_json::from_str(r#"["Ah", {"card_suit": "Hearts", "card_face": "Ten"}]"#).unwrap();
+        assert_eq!(
+            cards,
+            vec![CardData::new(CardFace::Ace, CardSuit::Hearts), CardData::new(CardFace::Ten, CardSuit::Hearts)]
+        );
+        assert!(serde_json::from_str::<CardData>(r#""Zz""#).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+
+    #[test]
+    fn test_distribution_is_exclusive() {
+        let mut hand = vec![
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+            CardData::new(CardFace::Nine, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Diamonds),
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+        ];
+        let discard = vec![
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+        ];
+        let distribution = hand
+            .calculate_outcome_distribution(None, Some(discard))
+            .unwrap();
+
+        assert_eq!(distribution.draws, 1081); // C(47, 2)
+        let total: f32 = distribution.probabilities.values().sum();
+        assert!((total - 1.0).abs() < 1e-5);
+
+        // Keeping trips can never finish below three of a kind
+        assert_eq!(distribution.probability(HandRank::HighCard), 0.0);
+        assert_eq!(distribution.probability(HandRank::Pair), 0.0);
+        // Only the last nine makes four of a kind: 46 draws include it
+        assert!((distribution.probability(HandRank::FourOfAKind) - 46.0 / 1081.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_discard_must_come_from_hand() {
+        let mut hand = vec![
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+            CardData::new(CardFace::Eight, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+        ];
+        let discard = vec![CardData::new(CardFace::Ace, CardSuit::Hearts)];
+        assert!(hand.calculate_outcome_distribution(None, Some(discard)).is_err());
+    }
+}
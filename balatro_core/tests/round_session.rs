@@ -0,0 +1,93 @@
+mod test {
+    use balatro_core::balatro_predictor::PredictionBackend;
+    use balatro_core::card_data::CardData;
+    use balatro_core::deck::Deck;
+    use balatro_core::error::PredictorError;
+    use balatro_core::joker::Joker;
+    use balatro_core::notation::Hand;
+    use balatro_core::rules::RuleSet;
+    use balatro_core::session::RoundSession;
+
+    fn cards(text: &str) -> Vec<CardData> {
+        text.parse::<Hand>().unwrap().into()
+    }
+
+    fn start() -> RoundSession {
+        RoundSession::start(cards("2c 7d 9h Js Ks"), Some(Joker::Woodie), Deck::standard(), RuleSet::DST).unwrap()
+    }
+
+    #[test]
+    fn test_discards_update_the_round() {
+        let mut session = start();
+        assert_eq!(session.discards_remaining, 2);
+
+        session.apply_discard(cards("2c 7d"), cards("Kh Kd")).unwrap();
+        assert_eq!(session.hand, cards("9h Js Ks Kh Kd"));
+        assert_eq!(session.discarded_cards(), cards("2c 7d"));
+        assert_eq!(session.discards_remaining, 1);
+
+        session.apply_discard(cards("9h"), cards("Kc")).unwrap();
+        assert_eq!(session.hand, cards("Js Ks Kh Kd Kc"));
+        assert_eq!(session.seen, cards("2c 7d 9h Js Ks Kh Kd Kc"));
+        assert_eq!(session.discards_remaining, 0);
+        assert_eq!(session.stages.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_discards_change_nothing() {
+        let mut session = start();
+        let before = session.clone();
+
+        let error = session.apply_discard(cards("2c"), cards("9h")).unwrap_err();
+        assert_eq!(error, PredictorError::DuplicateCard { cards: cards("9h") });
+        let error = session.apply_discard(cards("As"), cards("Ah")).unwrap_err();
+        assert_eq!(error, PredictorError::NotInHand { cards: cards("As") });
+        assert_eq!(session, before);
+
+        session.apply_discard(cards("2c"), cards("3c")).unwrap();
+        let error = session.apply_discard(cards("3c"), cards("2c")).unwrap_err();
+        assert_eq!(error, PredictorError::DiscardedInHand { cards: cards("2c") });
+
+        session.apply_discard(cards("3c"), cards("4c")).unwrap();
+        let error = session.apply_discard(cards("4c"), cards("5c")).unwrap_err();
+        assert_eq!(error, PredictorError::DiscardCount { max: 2, actual: 3 });
+    }
+
+    #[test]
+    fn test_discards_must_refill_the_hand() {
+        let mut session = start();
+        let before = session.clone();
+
+        // Throwing nothing away would use up a discard for nothing
+        let error = session.apply_discard(Vec::new(), Vec::new()).unwrap_err();
+        assert_eq!(error, PredictorError::DiscardSize { max: 5, actual: 0 });
+        // Drawing too few cards would leave the hand short for the rest of the round
+        let error = session.apply_discard(cards("2c 7d"), cards("Kh")).unwrap_err();
+        assert_eq!(error, PredictorError::DrawCount { expected: 2, actual: 1 });
+        let error = session.apply_discard(cards("2c"), cards("Kh Kd")).unwrap_err();
+        assert_eq!(error, PredictorError::DrawCount { expected: 1, actual: 2 });
+        assert_eq!(session, before);
+        assert_eq!(session.discards_remaining, 2);
+    }
+
+    #[test]
+    fn test_predictions_once_discards_run_out() {
+        let mut session = start();
+        session.apply_discard(cards("2c 7d"), cards("Kh Kd")).unwrap();
+        session.apply_discard(cards("9h"), cards("Kc")).unwrap();
+
+        // With no discard left, standing is the only option and it scores exactly the hand
+        let options = session.predictions(&PredictionBackend::Exact).unwrap();
+        assert_eq!(options.len(), 1);
+        assert!(options[0].discard.is_empty());
+        assert_eq!(options[0].kept, session.hand);
+
+        // Woodie adds 7 chips for each of the three cards discarded over the round
+        let score = session.score();
+        let without_joker = RoundSession {
+            joker: None,
+            ..session.clone()
+        };
+        assert_eq!(score.chips, without_joker.score().chips + 21);
+    }
+}
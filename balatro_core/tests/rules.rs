@@ -0,0 +1,163 @@
+mod test {
+    use balatro_core::balatro_predictor::ExactPredictor;
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_set::FACES;
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::deck::Deck;
+    use balatro_core::error::PredictorError;
+    use balatro_core::evaluator::evaluate_hand_with;
+    use balatro_core::exact::enumerate_draws;
+    use balatro_core::faces::CardFace;
+    use balatro_core::hand_rank::HandRank;
+    use balatro_core::rules::RuleSet;
+    use balatro_core::scoring::score_cards;
+
+    #[test]
+    fn test_dst_preset_matches_game_rules() {
+        let rules = RuleSet::default();
+        assert_eq!(rules, RuleSet::DST);
+        assert_eq!(rules.hand_size, 5);
+        assert_eq!(rules.discards, 2);
+        for rank in HandRank::ALL {
+            assert_eq!(rules.mult(rank), rank.mult());
+        }
+        for face in FACES {
+            assert_eq!(rules.chips(face), face.value() as u32);
+        }
+        assert!(HandRank::ALL.iter().all(|rank| rules.base_chips(*rank) == 0));
+        assert_eq!(RuleSet::preset("Balatro"), Some(RuleSet::BALATRO));
+        assert_eq!(RuleSet::preset("poker"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_partial_files_keep_dst_values() {
+        let rules: RuleSet = toml::from_str(
+            r#"
+            hand_size = 6
+            [mult]
+            Flush = 8
+            [chips]
+            Ace = 15
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rules.hand_size, 6);
+        assert_eq!(rules.discards, RuleSet::DST.discards);
+        assert_eq!(rules.mult(HandRank::Flush), 8);
+        assert_eq!(rules.mult(HandRank::Pair), 2);
+        assert_eq!(rules.chips(CardFace::Ace), 15);
+        assert_eq!(rules.chips(CardFace::King), 13);
+
+        let rules: RuleSet = serde_json::from_str(r#"{"ace_low": false, "mult": {"HighCard": 3}}"#).unwrap();
+        assert!(!rules.ace_low);
+        assert_eq!(rules.mult(HandRank::HighCard), 3);
+        assert_eq!(rules.chips, RuleSet::DST.chips);
+
+        // Whatever is written back reads the same
+        let text = serde_json::to_string(&RuleSet::BALATRO).unwrap();
+        assert_eq!(serde_json::from_str::<RuleSet>(&text).unwrap(), RuleSet::BALATRO);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for (_, rules) in RuleSet::PRESETS {
+            assert_eq!(rules.validate(), Ok(()));
+        }
+        let too_big = RuleSet {
+            hand_size: 40,
+            ..RuleSet::DST
+        };
+        assert!(matches!(too_big.validate(), Err(PredictorError::InvalidRules(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_invalid_rule_files() {
+        // Files are validated as they are read
+        let error = toml::from_str::<RuleSet>("hand_size = 40").unwrap_err();
+        assert!(error.to_string().contains("hand_size must be between 1 and 16"));
+        assert!(serde_json::from_str::<RuleSet>(r#"{"hand_size": 0}"#).is_err());
+        assert!(serde_json::from_str::<RuleSet>(r#"{"hand_size": 4}"#).is_err());
+        assert!(serde_json::from_str::<RuleSet>(r#"{"hand_size": 4, "max_discard_size": 4}"#).is_ok());
+
+        let path = std::env::temp_dir().join(format!("balatro_rules_{}.toml", std::process::id()));
+        std::fs::write(&path, "hand_size = 6\nmax_discard_size = 7").unwrap();
+        let error = RuleSet::load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.to_string().contains("max_discard_size 7 is bigger than the hand_size 6"));
+    }
+
+    #[test]
+    fn test_ace_low() {
+        let wheel = [
+            CardData::new(CardFace::Ace, CardSuit::Clubs),
+            CardData::new(CardFace::Two, CardSuit::Diamonds),
+            CardData::new(CardFace::Three, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Hearts),
+            CardData::new(CardFace::Five, CardSuit::Clubs),
+        ];
+        assert_eq!(evaluate_hand_with(&wheel, &RuleSet::DST).rank, HandRank::Straight);
+
+        let ace_high_only = RuleSet {
+            ace_low: false,
+            ..RuleSet::DST
+        };
+        assert_eq!(evaluate_hand_with(&wheel, &ace_high_only).rank, HandRank::HighCard);
+    }
+
+    #[test]
+    fn test_balatro_scoring() {
+        let pair = [
+            CardData::new(CardFace::King, CardSuit::Clubs),
+            CardData::new(CardFace::King, CardSuit::Diamonds),
+        ];
+        let score = score_cards(pair, HandRank::Pair, &RuleSet::BALATRO);
+        // A pair starts from 10 chips before the Kings add theirs
+        assert_eq!(score.chips, 10 + 20);
+        assert_eq!(score.mult, 2.0);
+
+        // Eight cards in hand: the best five of them score
+        let hand = vec![
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Clubs),
+            CardData::new(CardFace::Nine, CardSuit::Spades),
+            CardData::new(CardFace::Four, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Diamonds),
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Spades),
+            CardData::new(CardFace::Jack, CardSuit::Diamonds),
+        ];
+        let mut predictor = ExactPredictor::new(hand.clone(), Deck::standard(), RuleSet::BALATRO);
+        assert_eq!(predictor.calculate_full_house_probability(None).unwrap(), 1.0);
+        assert_eq!(predictor.calculate_expected_score(None, None, None).unwrap(), (40.0 + 35.0) * 4.0);
+
+        // The DST rules do not allow a hand that large
+        assert!(enumerate_draws(&hand, &[], &[], &Deck::standard(), None, &RuleSet::DST).is_err());
+    }
+
+    #[test]
+    fn test_discard_limits() {
+        let hand = vec![
+            CardData::new(CardFace::Two, CardSuit::Clubs),
+            CardData::new(CardFace::Five, CardSuit::Diamonds),
+            CardData::new(CardFace::Eight, CardSuit::Spades),
+            CardData::new(CardFace::Jack, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Clubs),
+        ];
+        let rules = RuleSet {
+            max_discard_size: 2,
+            discards: 1,
+            ..RuleSet::DST
+        };
+        let mut predictor = ExactPredictor::new(hand.clone(), Deck::standard(), rules);
+
+        let options = predictor.optimize_discard(None, None).unwrap();
+        assert_eq!(options.len(), 1 + 5 + 10);
+        assert!(options.iter().all(|option| option.discard.len() <= 2));
+
+        let too_many = Some(hand[..3].to_vec());
+        assert!(predictor.calculate_expected_score(None, too_many, None).is_err());
+        assert!(predictor.plan_discards(None, 2, None).is_err());
+    }
+}
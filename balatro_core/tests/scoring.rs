@@ -0,0 +1,52 @@
+mod test {
+    use balatro_core::card_data::{BalatroPredictor, CardData};
+    use balatro_core::card_suits::CardSuit;
+    use balatro_core::evaluator::evaluate_hand;
+    use balatro_core::faces::CardFace;
+    use balatro_core::scoring::score_evaluation;
+
+    #[test]
+    fn test_chips_times_mult() {
+        let pair = [
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Ten, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Clubs),
+        ];
+        let score = score_evaluation(&evaluate_hand(&pair));
+        assert_eq!(score.chips, 14);
+        assert_eq!(score.mult, 2.0);
+        assert_eq!(score.total, 28);
+
+        let flush = [
+            CardData::new(CardFace::Two, CardSuit::Hearts),
+            CardData::new(CardFace::Six, CardSuit::Hearts),
+            CardData::new(CardFace::Nine, CardSuit::Hearts),
+            CardData::new(CardFace::Jack, CardSuit::Hearts),
+            CardData::new(CardFace::King, CardSuit::Hearts),
+        ];
+        let score = score_evaluation(&evaluate_hand(&flush));
+        assert_eq!(score.chips, 41);
+        assert_eq!(score.mult, 6.0);
+        assert_eq!(score.total, 246);
+    }
+
+    #[test]
+    fn test_expected_score() {
+        let mut hand = vec![
+            CardData::new(CardFace::Seven, CardSuit::Clubs),
+            CardData::new(CardFace::Seven, CardSuit::Diamonds),
+            CardData::new(CardFace::King, CardSuit::Spades),
+            CardData::new(CardFace::Ten, CardSuit::Hearts),
+            CardData::new(CardFace::Four, CardSuit::Clubs),
+        ];
+        // Without a discard the hand is already finished
+        assert_eq!(hand.calculate_expected_score(None, None, None).unwrap(), 28.0);
+
+        // Throwing away the four can only land on a pair or better
+        let discard = vec![CardData::new(CardFace::Four, CardSuit::Clubs)];
+        let expected = hand.calculate_expected_score(None, Some(discard), None).unwrap();
+        assert!(expected >= 28.0);
+    }
+}
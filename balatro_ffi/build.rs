@@ -0,0 +1,16 @@
+use std::path::Path;
+
+/// Writes `include/balatro.h` from the `extern "C"` API in `src/lib.rs`.
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("cbindgen.toml should be valid");
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("the C API should be exportable")
+        .write_to_file(Path::new(&crate_dir).join("include").join("balatro.h"));
+}
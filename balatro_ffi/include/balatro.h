@@ -0,0 +1,221 @@
+/* Generated by cbindgen from balatro_ffi/src/lib.rs; do not edit by hand. */
+
+#ifndef BALATRO_H
+#define BALATRO_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Number of hand types, and so of probabilities in an outcome distribution.
+ */
+#define BALATRO_HAND_RANKS 10
+
+/**
+ * Number of card ids.
+ */
+#define BALATRO_CARDS 52
+
+/**
+ * Result of every call. Anything but `Ok` means nothing was written to the out pointers,
+ * except for `BufferTooSmall`, which still reports the size needed.
+ */
+typedef enum BalatroStatus {
+  BALATRO_STATUS_OK = 0,
+  /**
+   * A required pointer was null.
+   */
+  BALATRO_STATUS_NULL_POINTER = 1,
+  /**
+   * A card id was not below 52.
+   */
+  BALATRO_STATUS_INVALID_CARD = 2,
+  /**
+   * The output buffer cannot hold the result.
+   */
+  BALATRO_STATUS_BUFFER_TOO_SMALL = 3,
+  /**
+   * The hand holds more cards than the rules allow.
+   */
+  BALATRO_STATUS_HAND_SIZE = 4,
+  /**
+   * The hand holds a card more often than the deck has copies of it.
+   */
+  BALATRO_STATUS_DUPLICATE_CARD = 5,
+  /**
+   * A card in the hand was also discarded earlier in the round.
+   */
+  BALATRO_STATUS_DISCARDED_IN_HAND = 6,
+  /**
+   * A card has no copy left in the deck.
+   */
+  BALATRO_STATUS_MISSING_FROM_DECK = 7,
+  /**
+   * A card to discard is not in the hand.
+   */
+  BALATRO_STATUS_NOT_IN_HAND = 8,
+  /**
+   * A discard throws away more cards than the rules allow at once.
+   */
+  BALATRO_STATUS_DISCARD_SIZE = 9,
+  /**
+   * More discards are planned than the rules allow in a round.
+   */
+  BALATRO_STATUS_DISCARD_COUNT = 10,
+  /**
+   * The deck runs out before the hand is full again.
+   */
+  BALATRO_STATUS_NOT_ENOUGH_CARDS = 11,
+  /**
+   * Any other failure.
+   */
+  BALATRO_STATUS_OTHER = 12,
+  /**
+   * The library panicked; the session should be freed.
+   */
+  BALATRO_STATUS_PANIC = 13,
+} BalatroStatus;
+
+/**
+ * Rule set a session starts with, passed to [`balatro_session_new`].
+ */
+typedef enum BalatroRules {
+  BALATRO_RULES_DST = 0,
+  BALATRO_RULES_BALATRO = 1,
+} BalatroRules;
+
+/**
+ * The deck and rules predictions are made against. Opaque to C.
+ */
+typedef struct BalatroSession BalatroSession;
+
+/**
+ * The best hand in a set of cards and its score.
+ */
+typedef struct BalatroEvaluation {
+  /**
+   * Hand type, from 0 for High Card up to 9 for Royal Flush.
+   */
+  uint32_t rank;
+  uint32_t chips;
+  double mult;
+  /**
+   * Chips × mult, rounded down.
+   */
+  uint32_t score;
+} BalatroEvaluation;
+
+/**
+ * One choice of cards to throw away.
+ */
+typedef struct BalatroDiscardOption {
+  /**
+   * Bit `i` is set when the card at position `i` of the hand is thrown away.
+   */
+  uint32_t discard_mask;
+  float expected_score;
+  /**
+   * Probability of finishing as every hand type, indexed like [`BalatroEvaluation::rank`].
+   */
+  float probabilities[BALATRO_HAND_RANKS];
+} BalatroDiscardOption;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * A session with the standard 52 card deck under `rules`, one of the [`BalatroRules`]
+ * values, or null if `rules` is not one of them. Free it with [`balatro_session_free`].
+ */
+struct BalatroSession *balatro_session_new(uint32_t rules);
+
+/**
+ * Frees a session. Null is ignored.
+ *
+ * # Safety
+ * `session` must be null or come from [`balatro_session_new`] and not have been freed.
+ */
+void balatro_session_free(struct BalatroSession *session);
+
+/**
+ * Adds `copies` of `card` to the session's deck.
+ *
+ * # Safety
+ * `session` must be null or a live session.
+ */
+enum BalatroStatus balatro_session_add_card(struct BalatroSession *session,
+                                            uint8_t card,
+                                            uint32_t copies);
+
+/**
+ * Takes every copy of `card` out of the session's deck.
+ *
+ * # Safety
+ * `session` must be null or a live session.
+ */
+enum BalatroStatus balatro_session_remove_card(struct BalatroSession *session, uint8_t card);
+
+/**
+ * Evaluates and scores the `hand_len` cards at `hand` into `out`.
+ *
+ * # Safety
+ * `session` must be null or a live session, `hand` must point to `hand_len` card ids and
+ * `out` must be null or writable.
+ */
+enum BalatroStatus balatro_evaluate_hand(struct BalatroSession *session,
+                                         const uint8_t *hand,
+                                         size_t hand_len,
+                                         struct BalatroEvaluation *out);
+
+/**
+ * Writes the probability of finishing as every hand type into `out`, indexed like
+ * [`BalatroEvaluation::rank`], after throwing away `discard` from `hand` and drawing back
+ * up to a full hand. `discarded` holds the cards thrown away earlier in the round.
+ *
+ * `out_len` must be at least [`BALATRO_HAND_RANKS`].
+ *
+ * # Safety
+ * `session` must be null or a live session, every card pointer must point to as many
+ * card ids as its length and `out` must point to `out_len` writable floats.
+ */
+enum BalatroStatus balatro_outcome_distribution(struct BalatroSession *session,
+                                                const uint8_t *hand,
+                                                size_t hand_len,
+                                                const uint8_t *discarded,
+                                                size_t discarded_len,
+                                                const uint8_t *discard,
+                                                size_t discard_len,
+                                                float *out,
+                                                size_t out_len);
+
+/**
+ * Writes every choice of cards to throw away from `hand` into `out`, best expected score
+ * first, and their number into `written`. `discarded` holds the cards thrown away earlier
+ * in the round.
+ *
+ * A hand of `n` cards has at most `2^n` options. When `out_len` is too small nothing is
+ * written to `out`, `written` is set to the number needed and `BufferTooSmall` is returned.
+ *
+ * # Safety
+ * `session` must be null or a live session, every card pointer must point to as many
+ * card ids as its length, `out` must point to `out_len` writable options and `written`
+ * must be null or writable.
+ */
+enum BalatroStatus balatro_optimize_discard(struct BalatroSession *session,
+                                            const uint8_t *hand,
+                                            size_t hand_len,
+                                            const uint8_t *discarded,
+                                            size_t discarded_len,
+                                            struct BalatroDiscardOption *out,
+                                            size_t out_len,
+                                            size_t *written);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BALATRO_H */
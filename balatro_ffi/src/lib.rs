@@ -0,0 +1,376 @@
+//! C ABI for the predictor.
+//!
+//! Cards are ids in `0..52`, `13 * suit + face`, with suits in Clubs, Diamonds, Hearts,
+//! Spades order and faces from Two up to Ace, as in [`CardSet::index`]. Predictions are made
+//! against a [`BalatroSession`], which holds the deck and rules. Every function returns a
+//! [`BalatroStatus`] and writes its result through an out pointer; none of them panics
+//! across the boundary.
+//!
+//! The header is `include/balatro.h`, regenerated by cbindgen on every build.
+
+use balatro_core::balatro_predictor::ExactPredictor;
+use balatro_core::card_data::{BalatroPredictor, CardData};
+use balatro_core::card_set::CardSet;
+use balatro_core::deck::{Deck, DeckChange};
+use balatro_core::error::PredictorError;
+use balatro_core::evaluator;
+use balatro_core::hand_rank::HandRank;
+use balatro_core::joker::RoundContext;
+use balatro_core::optimizer::DiscardOption;
+use balatro_core::rules::RuleSet;
+use balatro_core::scoring;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Number of hand types, and so of probabilities in an outcome distribution.
+pub const BALATRO_HAND_RANKS: usize = 10;
+const _: () = assert!(BALATRO_HAND_RANKS == HandRank::COUNT);
+
+/// Number of card ids.
+pub const BALATRO_CARDS: u8 = 52;
+
+/// Result of every call. Anything but `Ok` means nothing was written to the out pointers,
+/// except for `BufferTooSmall`, which still reports the size needed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalatroStatus {
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// A card id was not below 52.
+    InvalidCard = 2,
+    /// The output buffer cannot hold the result.
+    BufferTooSmall = 3,
+    /// The hand holds more cards than the rules allow.
+    HandSize = 4,
+    /// The hand holds a card more often than the deck has copies of it.
+    DuplicateCard = 5,
+    /// A card in the hand was also discarded earlier in the round.
+    DiscardedInHand = 6,
+    /// A card has no copy left in the deck.
+    MissingFromDeck = 7,
+    /// A card to discard is not in the hand.
+    NotInHand = 8,
+    /// A discard throws away more cards than the rules allow at once.
+    DiscardSize = 9,
+    /// More discards are planned than the rules allow in a round.
+    DiscardCount = 10,
+    /// The deck runs out before the hand is full again.
+    NotEnoughCards = 11,
+    /// Any other failure.
+    Other = 12,
+    /// The library panicked; the session should be freed.
+    Panic = 13,
+}
+
+impl From<PredictorError> for BalatroStatus {
+    fn from(error: PredictorError) -> Self {
+        match error {
+            PredictorError::HandSize { .. } => BalatroStatus::HandSize,
+            PredictorError::DuplicateCard { .. } => BalatroStatus::DuplicateCard,
+            PredictorError::DiscardedInHand { .. } => BalatroStatus::DiscardedInHand,
+            PredictorError::MissingFromDeck { .. } => BalatroStatus::MissingFromDeck,
+            PredictorError::NotInHand { .. } => BalatroStatus::NotInHand,
+            PredictorError::DiscardSize { .. } => BalatroStatus::DiscardSize,
+            PredictorError::DiscardCount { .. } => BalatroStatus::DiscardCount,
+            PredictorError::NotEnoughCards { .. } => BalatroStatus::NotEnoughCards,
+            PredictorError::DrawCount { .. }
+            | PredictorError::NoRound
+            | PredictorError::InvalidRules(_)
+            | PredictorError::Other(_) => BalatroStatus::Other,
+        }
+    }
+}
+
+impl From<anyhow::Error> for BalatroStatus {
+    fn from(error: anyhow::Error) -> Self {
+        BalatroStatus::from(PredictorError::from(error))
+    }
+}
+
+/// Rule set a session starts with, passed to [`balatro_session_new`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalatroRules {
+    Dst = 0,
+    Balatro = 1,
+}
+
+/// The deck and rules predictions are made against. Opaque to C.
+pub struct BalatroSession {
+    deck: Deck,
+    rules: RuleSet,
+}
+
+/// The best hand in a set of cards and its score.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BalatroEvaluation {
+    /// Hand type, from 0 for High Card up to 9 for Royal Flush.
+    pub rank: u32,
+    pub chips: u32,
+    pub mult: f64,
+    /// Chips × mult, rounded down.
+    pub score: u32,
+}
+
+/// One choice of cards to throw away.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BalatroDiscardOption {
+    /// Bit `i` is set when the card at position `i` of the hand is thrown away.
+    pub discard_mask: u32,
+    pub expected_score: f32,
+    /// Probability of finishing as every hand type, indexed like [`BalatroEvaluation::rank`].
+    pub probabilities: [f32; BALATRO_HAND_RANKS],
+}
+
+/// Runs `call`, turning a panic into [`BalatroStatus::Panic`].
+fn guard(call: impl FnOnce() -> Result<(), BalatroStatus>) -> BalatroStatus {
+    match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(())) => BalatroStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => BalatroStatus::Panic,
+    }
+}
+
+/// The cards behind `len` ids at `ids`, which may be null when `len` is 0.
+///
+/// # Safety
+/// `ids` must point to `len` readable bytes when `len` is not 0.
+unsafe fn cards(ids: *const u8, len: usize) -> Result<Vec<CardData>, BalatroStatus> {
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    if ids.is_null() {
+        return Err(BalatroStatus::NullPointer);
+    }
+    // SAFETY: the caller guarantees `len` readable bytes at `ids`
+    let ids = unsafe { std::slice::from_raw_parts(ids, len) };
+    ids.iter().map(|&id| card(id)).collect()
+}
+
+fn card(id: u8) -> Result<CardData, BalatroStatus> {
+    if id < BALATRO_CARDS { Ok(CardSet::card(id)) } else { Err(BalatroStatus::InvalidCard) }
+}
+
+/// The session behind `session`, or `NullPointer`.
+///
+/// # Safety
+/// `session` must be null or come from [`balatro_session_new`] and not have been freed.
+unsafe fn session<'a>(session: *mut BalatroSession) -> Result<&'a mut BalatroSession, BalatroStatus> {
+    // SAFETY: the caller guarantees a live session or null
+    unsafe { session.as_mut() }.ok_or(BalatroStatus::NullPointer)
+}
+
+fn optional(cards: Vec<CardData>) -> Option<Vec<CardData>> {
+    if cards.is_empty() { None } else { Some(cards) }
+}
+
+/// Positions of `option`'s discard within `hand`, as a bit mask.
+fn discard_mask(hand: &[CardData], option: &DiscardOption) -> u32 {
+    let mut mask = 0;
+    for card in &option.discard {
+        if let Some(position) = (0..hand.len()).find(|&i| mask & 1 << i == 0 && hand[i] == *card) {
+            mask |= 1 << position;
+        }
+    }
+    mask
+}
+
+/// A session with the standard 52 card deck under `rules`, one of the [`BalatroRules`]
+/// values, or null if `rules` is not one of them. Free it with [`balatro_session_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn balatro_session_new(rules: u32) -> *mut BalatroSession {
+    let rules = match rules {
+        rules if rules == BalatroRules::Dst as u32 => RuleSet::DST,
+        rules if rules == BalatroRules::Balatro as u32 => RuleSet::BALATRO,
+        _ => return std::ptr::null_mut(),
+    };
+    catch_unwind(|| {
+        Box::into_raw(Box::new(BalatroSession {
+            deck: Deck::standard(),
+            rules,
+        }))
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Frees a session. Null is ignored.
+///
+/// # Safety
+/// `session` must be null or come from [`balatro_session_new`] and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn balatro_session_free(session: *mut BalatroSession) {
+    if !session.is_null() {
+        // SAFETY: the caller guarantees the session is live and gives up ownership of it
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(unsafe { Box::from_raw(session) })));
+    }
+}
+
+/// Adds `copies` of `card` to the session's deck.
+///
+/// # Safety
+/// `session` must be null or a live session.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn balatro_session_add_card(session: *mut BalatroSession, card: u8, copies: u32) -> BalatroStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller
+        let session = unsafe { self::session(session) }?;
+        session.deck.apply(&DeckChange::AddCard {
+            card: self::card(card)?,
+            copies,
+        });
+        Ok(())
+    })
+}
+
+/// Takes every copy of `card` out of the session's deck.
+///
+/// # Safety
+/// `session` must be null or a live session.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn balatro_session_remove_card(session: *mut BalatroSession, card: u8) -> BalatroStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller
+        let session = unsafe { self::session(session) }?;
+        session.deck.apply(&DeckChange::RemoveCard { card: self::card(card)? });
+        Ok(())
+    })
+}
+
+/// Evaluates and scores the `hand_len` cards at `hand` into `out`.
+///
+/// # Safety
+/// `session` must be null or a live session, `hand` must point to `hand_len` card ids and
+/// `out` must be null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn balatro_evaluate_hand(
+    session: *mut BalatroSession,
+    hand: *const u8,
+    hand_len: usize,
+    out: *mut BalatroEvaluation,
+) -> BalatroStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller
+        let session = unsafe { self::session(session) }?;
+        let hand = unsafe { cards(hand, hand_len) }?;
+        let out = unsafe { out.as_mut() }.ok_or(BalatroStatus::NullPointer)?;
+
+        let evaluation = evaluator::evaluate_hand_with(&hand, &session.rules);
+        let round = RoundContext {
+            starting_hand: &hand,
+            stages: &[],
+            final_hand: &hand,
+        };
+        let score = scoring::score_round(&evaluation, &round, None, &session.rules);
+        *out = BalatroEvaluation {
+            rank: evaluation.rank.index() as u32,
+            chips: score.chips,
+            mult: score.mult,
+            score: score.total,
+        };
+        Ok(())
+    })
+}
+
+/// Writes the probability of finishing as every hand type into `out`, indexed like
+/// [`BalatroEvaluation::rank`], after throwing away `discard` from `hand` and drawing back
+/// up to a full hand. `discarded` holds the cards thrown away earlier in the round.
+///
+/// `out_len` must be at least [`BALATRO_HAND_RANKS`].
+///
+/// # Safety
+/// `session` must be null or a live session, every card pointer must point to as many
+/// card ids as its length and `out` must point to `out_len` writable floats.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn balatro_outcome_distribution(
+    session: *mut BalatroSession,
+    hand: *const u8,
+    hand_len: usize,
+    discarded: *const u8,
+    discarded_len: usize,
+    discard: *const u8,
+    discard_len: usize,
+    out: *mut f32,
+    out_len: usize,
+) -> BalatroStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller
+        let session = unsafe { self::session(session) }?;
+        let hand = unsafe { cards(hand, hand_len) }?;
+        let discarded = unsafe { cards(discarded, discarded_len) }?;
+        let discard = unsafe { cards(discard, discard_len) }?;
+        if out.is_null() {
+            return Err(BalatroStatus::NullPointer);
+        }
+        if out_len < BALATRO_HAND_RANKS {
+            return Err(BalatroStatus::BufferTooSmall);
+        }
+
+        let distribution = ExactPredictor::new(hand, session.deck.clone(), session.rules.clone())
+            .calculate_outcome_distribution(optional(discarded), optional(discard))?;
+        // SAFETY: the caller guarantees `out_len` writable floats, checked to be enough
+        let out = unsafe { std::slice::from_raw_parts_mut(out, BALATRO_HAND_RANKS) };
+        for rank in HandRank::ALL {
+            out[rank.index()] = distribution.probability(rank);
+        }
+        Ok(())
+    })
+}
+
+/// Writes every choice of cards to throw away from `hand` into `out`, best expected score
+/// first, and their number into `written`. `discarded` holds the cards thrown away earlier
+/// in the round.
+///
+/// A hand of `n` cards has at most `2^n` options. When `out_len` is too small nothing is
+/// written to `out`, `written` is set to the number needed and `BufferTooSmall` is returned.
+///
+/// # Safety
+/// `session` must be null or a live session, every card pointer must point to as many
+/// card ids as its length, `out` must point to `out_len` writable options and `written`
+/// must be null or writable.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn balatro_optimize_discard(
+    session: *mut BalatroSession,
+    hand: *const u8,
+    hand_len: usize,
+    discarded: *const u8,
+    discarded_len: usize,
+    out: *mut BalatroDiscardOption,
+    out_len: usize,
+    written: *mut usize,
+) -> BalatroStatus {
+    guard(|| {
+        // SAFETY: forwarded from the caller
+        let session = unsafe { self::session(session) }?;
+        let hand = unsafe { cards(hand, hand_len) }?;
+        let discarded = unsafe { cards(discarded, discarded_len) }?;
+        let written = unsafe { written.as_mut() }.ok_or(BalatroStatus::NullPointer)?;
+        if out.is_null() && out_len > 0 {
+            return Err(BalatroStatus::NullPointer);
+        }
+
+        let options = ExactPredictor::new(hand.clone(), session.deck.clone(), session.rules.clone())
+            .optimize_discard(optional(discarded), None)?;
+        *written = options.len();
+        if out_len < options.len() {
+            return Err(BalatroStatus::BufferTooSmall);
+        }
+        // SAFETY: the caller guarantees `out_len` writable options, checked to be enough
+        let out = unsafe { std::slice::from_raw_parts_mut(out, options.len()) };
+        for (slot, option) in out.iter_mut().zip(&options) {
+            let mut probabilities = [0.0; BALATRO_HAND_RANKS];
+            for rank in HandRank::ALL {
+                probabilities[rank.index()] = option.distribution.probability(rank);
+            }
+            *slot = BalatroDiscardOption {
+                discard_mask: discard_mask(&hand, option),
+                expected_score: option.expected_score,
+                probabilities,
+            };
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,142 @@
+mod test {
+    use balatro::*;
+    use std::ptr;
+
+    /// Card id of `face` (2 to 14 for Ace) in suit `suit` (Clubs, Diamonds, Hearts, Spades).
+    fn id(face: u8, suit: u8) -> u8 {
+        13 * suit + face - 2
+    }
+
+    const HEARTS: u8 = 2;
+    const SPADES: u8 = 3;
+
+    #[test]
+    fn test_evaluate_hand() {
+        let session = balatro_session_new(BalatroRules::Dst as u32);
+        let hand = [id(2, HEARTS), id(7, HEARTS), id(9, HEARTS), id(13, HEARTS), id(14, HEARTS)];
+        let mut evaluation = BalatroEvaluation::default();
+        let status = unsafe { balatro_evaluate_hand(session, hand.as_ptr(), hand.len(), &mut evaluation) };
+        assert_eq!(status, BalatroStatus::Ok);
+        assert_eq!(evaluation.rank, 5);
+        assert_eq!(evaluation.score, (evaluation.chips as f64 * evaluation.mult) as u32);
+        unsafe { balatro_session_free(session) };
+    }
+
+    #[test]
+    fn test_outcome_distribution() {
+        let session = balatro_session_new(BalatroRules::Dst as u32);
+        // Four hearts, drawing one card for the flush
+        let hand = [id(2, HEARTS), id(7, HEARTS), id(9, HEARTS), id(13, HEARTS)];
+        let mut probabilities = [0.0f32; BALATRO_HAND_RANKS];
+        let status = unsafe {
+            balatro_outcome_distribution(
+                session,
+                hand.as_ptr(),
+                hand.len(),
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                probabilities.as_mut_ptr(),
+                probabilities.len(),
+            )
+        };
+        assert_eq!(status, BalatroStatus::Ok);
+        assert!((probabilities[5] - 9.0 / 48.0).abs() < 1e-6);
+        assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-4);
+
+        // Without the ace of spades in the deck
+        assert_eq!(unsafe { balatro_session_remove_card(session, id(14, SPADES)) }, BalatroStatus::Ok);
+        let status = unsafe {
+            balatro_outcome_distribution(
+                session,
+                hand.as_ptr(),
+                hand.len(),
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                probabilities.as_mut_ptr(),
+                probabilities.len(),
+            )
+        };
+        assert_eq!(status, BalatroStatus::Ok);
+        assert!((probabilities[5] - 9.0 / 47.0).abs() < 1e-6);
+        unsafe { balatro_session_free(session) };
+    }
+
+    #[test]
+    fn test_optimize_discard() {
+        let session = balatro_session_new(BalatroRules::Dst as u32);
+        let hand = [id(2, HEARTS), id(7, HEARTS), id(9, HEARTS), id(13, HEARTS), id(13, SPADES)];
+        let discarded = [id(3, 0)];
+        let mut written = 0;
+        let status = unsafe {
+            balatro_optimize_discard(
+                session,
+                hand.as_ptr(),
+                hand.len(),
+                discarded.as_ptr(),
+                discarded.len(),
+                ptr::null_mut(),
+                0,
+                &mut written,
+            )
+        };
+        assert_eq!(status, BalatroStatus::BufferTooSmall);
+        assert_eq!(written, 32);
+
+        let mut options = vec![BalatroDiscardOption::default(); written];
+        let status = unsafe {
+            balatro_optimize_discard(
+                session,
+                hand.as_ptr(),
+                hand.len(),
+                discarded.as_ptr(),
+                discarded.len(),
+                options.as_mut_ptr(),
+                options.len(),
+                &mut written,
+            )
+        };
+        assert_eq!(status, BalatroStatus::Ok);
+        assert!(options.windows(2).all(|pair| pair[0].expected_score >= pair[1].expected_score));
+        let mut masks: Vec<u32> = options.iter().map(|option| option.discard_mask).collect();
+        masks.sort();
+        assert_eq!(masks, (0..32).collect::<Vec<u32>>());
+        unsafe { balatro_session_free(session) };
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut evaluation = BalatroEvaluation::default();
+        let hand = [id(14, SPADES), id(14, SPADES)];
+        let status = unsafe { balatro_evaluate_hand(ptr::null_mut(), hand.as_ptr(), hand.len(), &mut evaluation) };
+        assert_eq!(status, BalatroStatus::NullPointer);
+        assert!(balatro_session_new(7).is_null());
+
+        let session = balatro_session_new(BalatroRules::Balatro as u32);
+        assert_eq!(unsafe { balatro_session_add_card(session, 52, 1) }, BalatroStatus::InvalidCard);
+
+        let mut probabilities = [0.0f32; BALATRO_HAND_RANKS];
+        let distribution = |hand: &[u8], probabilities: &mut [f32]| unsafe {
+            balatro_outcome_distribution(
+                session,
+                hand.as_ptr(),
+                hand.len(),
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                probabilities.as_mut_ptr(),
+                probabilities.len(),
+            )
+        };
+        assert_eq!(distribution(&hand, &mut probabilities), BalatroStatus::DuplicateCard);
+        assert_eq!(distribution(&hand[..1], &mut probabilities[..3]), BalatroStatus::BufferTooSmall);
+
+        assert_eq!(unsafe { balatro_session_remove_card(session, id(14, SPADES)) }, BalatroStatus::Ok);
+        assert_eq!(distribution(&hand[..1], &mut probabilities), BalatroStatus::MissingFromDeck);
+        unsafe { balatro_session_free(session) };
+    }
+}
@@ -0,0 +1,634 @@
+//! Python bindings to the predictor, built by maturin as the `balatro_py` module.
+//!
+//! Anywhere a hand is taken it can be text such as `"Ah Kd 10c"` or a sequence of cards,
+//! where every card is a `CardData`, text such as `"Ah"` or a `(face, suit)` tuple such as
+//! `("A", "h")`, `("Ace", "Hearts")` or `(14, "h")`. Hand types, jokers, faces and suits
+//! are named as in the Rust enums, ignoring case. Bulk results come back as lists, and as
+//! `array.array` buffers of doubles that `numpy.asarray` reads without copying.
+
+use balatro_core::balatro_predictor::ExactPredictor;
+use balatro_core::card_data::{BalatroPredictor, CardData};
+use balatro_core::card_set::{CardSet, FACES, SUITS};
+use balatro_core::card_suits::CardSuit;
+use balatro_core::deck::Deck;
+use balatro_core::distribution::OutcomeDistribution;
+use balatro_core::evaluator;
+use balatro_core::faces::CardFace;
+use balatro_core::hand_rank::HandRank;
+use balatro_core::joker::{Joker, RoundContext};
+use balatro_core::monte_carlo::{self, Estimate, MonteCarloConfig, SampledOutcome};
+use balatro_core::notation::Hand;
+use balatro_core::optimizer::DiscardOption;
+use balatro_core::rules::RuleSet;
+use balatro_core::scoring;
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+create_exception!(
+    balatro_py,
+    PredictorError,
+    PyValueError,
+    "Raised when a prediction cannot be made from the cards given; `args` is `(message, code)`."
+);
+
+impl PredictorError {
+    fn from_core(error: impl Into<balatro_core::error::PredictorError>) -> PyErr {
+        let error = error.into();
+        PredictorError::new_err((error.to_string(), error.code()))
+    }
+}
+
+fn value_error(error: impl ToString) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// The value of `all` whose name is `name`, ignoring case.
+fn named<T: Copy + Debug>(all: &[T], name: &str, what: &str) -> PyResult<T> {
+    all.iter()
+        .find(|value| format!("{:?}", value).eq_ignore_ascii_case(name))
+        .copied()
+        .ok_or_else(|| value_error(format!("Unknown {} {:?}", what, name)))
+}
+
+fn name(value: impl Debug) -> String {
+    format!("{:?}", value)
+}
+
+fn face(value: &Bound<'_, PyAny>) -> PyResult<CardFace> {
+    if let Ok(rank) = value.extract::<u8>() {
+        return CardFace::from_rank(rank).ok_or_else(|| value_error(format!("No face has rank {}", rank)));
+    }
+    let text: String = value.extract()?;
+    text.parse().or_else(|_| named(&FACES, &text, "face"))
+}
+
+fn suit(value: &Bound<'_, PyAny>) -> PyResult<CardSuit> {
+    let text: String = value.extract()?;
+    text.parse().or_else(|_| named(&SUITS, &text, "suit"))
+}
+
+fn card(value: &Bound<'_, PyAny>) -> PyResult<CardData> {
+    if let Ok(card) = value.cast::<PyCard>() {
+        return Ok(card.get().0);
+    }
+    if let Ok(text) = value.extract::<String>() {
+        return text.parse().map_err(value_error);
+    }
+    let (face, suit): (Bound<'_, PyAny>, Bound<'_, PyAny>) = value.extract()?;
+    Ok(CardData::new(self::face(&face)?, self::suit(&suit)?))
+}
+
+fn hand(value: &Bound<'_, PyAny>) -> PyResult<Vec<CardData>> {
+    if let Ok(text) = value.extract::<String>() {
+        return text.parse::<Hand>().map(Vec::from).map_err(value_error);
+    }
+    value.try_iter()?.map(|item| card(&item?)).collect()
+}
+
+fn optional_hand(value: Option<&Bound<'_, PyAny>>) -> PyResult<Vec<CardData>> {
+    value.map(hand).transpose().map(Option::unwrap_or_default)
+}
+
+fn optional(cards: &[CardData]) -> Option<Vec<CardData>> {
+    if cards.is_empty() { None } else { Some(cards.to_vec()) }
+}
+
+/// A preset name such as `"dst"`, or the path of a `.toml` or `.json` rule set.
+fn rules(rules: Option<&str>) -> PyResult<RuleSet> {
+    match rules {
+        None => Ok(RuleSet::default()),
+        Some(name) => match RuleSet::preset(name) {
+            Some(rules) => Ok(rules),
+            None => RuleSet::load(name).map_err(value_error),
+        },
+    }
+}
+
+fn joker(joker: Option<&str>) -> PyResult<Option<Joker>> {
+    joker.map(|joker| named(&Joker::ALL, joker, "joker")).transpose()
+}
+
+fn deck(deck: Option<&Bound<'_, PyAny>>) -> PyResult<Deck> {
+    Ok(match deck {
+        Some(deck) => hand(deck)?.into_iter().collect(),
+        None => Deck::standard(),
+    })
+}
+
+fn cards(cards: &[CardData]) -> Vec<PyCard> {
+    cards.iter().copied().map(PyCard).collect()
+}
+
+/// An `array.array` of doubles.
+fn array<'py>(py: Python<'py>, values: Vec<f64>) -> PyResult<Bound<'py, PyAny>> {
+    py.import("array")?.getattr("array")?.call1(("d", values))
+}
+
+/// A playing card, with any enhancement, edition and seal.
+#[pyclass(name = "CardData", module = "balatro_py", frozen, eq, skip_from_py_object)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct PyCard(CardData);
+
+#[pymethods]
+impl PyCard {
+    #[new]
+    fn new(face: &Bound<'_, PyAny>, suit: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyCard(CardData::new(self::face(face)?, self::suit(suit)?)))
+    }
+
+    /// Reads a card such as `"Ah"`, `"10s"`, `"Td"` or `"K♥"`.
+    #[staticmethod]
+    fn parse(text: &str) -> PyResult<Self> {
+        text.parse().map(PyCard).map_err(value_error)
+    }
+
+    /// The card with id `id` in `0..52`, `13 * suit + face`.
+    #[staticmethod]
+    fn from_id(id: u8) -> PyResult<Self> {
+        if id < 52 { Ok(PyCard(CardSet::card(id))) } else { Err(value_error(format!("No card has id {}", id))) }
+    }
+
+    #[getter]
+    fn face(&self) -> String {
+        name(self.0.card_face)
+    }
+
+    #[getter]
+    fn suit(&self) -> String {
+        name(self.0.card_suit)
+    }
+
+    /// From 2 up to 14 for an Ace.
+    #[getter]
+    fn rank(&self) -> u8 {
+        self.0.card_face.rank()
+    }
+
+    #[getter]
+    fn id(&self) -> u8 {
+        CardSet::index(&self.0)
+    }
+
+    #[getter]
+    fn enhancement(&self) -> Option<String> {
+        self.0.enhancement.map(name)
+    }
+
+    #[getter]
+    fn edition(&self) -> Option<String> {
+        self.0.edition.map(name)
+    }
+
+    #[getter]
+    fn seal(&self) -> Option<String> {
+        self.0.seal.map(name)
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (CardSet::index(&self.0), self.0.enhancement, self.0.edition, self.0.seal).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CardData('{}')", self.0)
+    }
+}
+
+/// The best hand in a set of cards and its chips × mult score.
+#[pyclass(name = "HandEvaluation", module = "balatro_py", frozen, get_all)]
+pub struct PyHandEvaluation {
+    rank: String,
+    /// Cards that make up the hand, strongest group first.
+    scoring_cards: Vec<PyCard>,
+    /// Cards that are not part of the hand, highest first.
+    kickers: Vec<PyCard>,
+    /// Tie-breaking key; a higher key beats a lower one and equal keys tie.
+    key: u32,
+    chips: u32,
+    mult: f64,
+    score: u32,
+}
+
+#[pymethods]
+impl PyHandEvaluation {
+    fn __repr__(&self) -> String {
+        format!("HandEvaluation(rank='{}', score={})", self.rank, self.score)
+    }
+}
+
+/// Probability of finishing with each hand type.
+#[pyclass(name = "OutcomeDistribution", module = "balatro_py", frozen)]
+pub struct PyOutcomeDistribution(OutcomeDistribution);
+
+#[pymethods]
+impl PyOutcomeDistribution {
+    /// Probability of every hand type by name.
+    #[getter]
+    fn probabilities(&self) -> BTreeMap<String, f32> {
+        HandRank::ALL.iter().map(|&rank| (name(rank), self.0.probability(rank))).collect()
+    }
+
+    /// Number of draws the distribution was built from.
+    #[getter]
+    fn draws(&self) -> u64 {
+        self.0.draws
+    }
+
+    fn probability(&self, rank: &str) -> PyResult<f32> {
+        Ok(self.0.probability(named(&HandRank::ALL, rank, "hand type")?))
+    }
+
+    /// The probabilities in `HAND_RANKS` order, as an `array.array` of doubles.
+    fn to_array<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        array(py, HandRank::ALL.iter().map(|&rank| self.0.probability(rank) as f64).collect())
+    }
+
+    fn __getitem__(&self, rank: &str) -> PyResult<f32> {
+        self.probability(rank)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("OutcomeDistribution(draws={})", self.0.draws)
+    }
+}
+
+/// One choice of cards to throw away, with what it leads to.
+#[pyclass(name = "DiscardOption", module = "balatro_py", frozen)]
+pub struct PyDiscardOption(DiscardOption);
+
+#[pymethods]
+impl PyDiscardOption {
+    #[getter]
+    fn discard(&self) -> Vec<PyCard> {
+        cards(&self.0.discard)
+    }
+
+    #[getter]
+    fn kept(&self) -> Vec<PyCard> {
+        cards(&self.0.kept)
+    }
+
+    #[getter]
+    fn distribution(&self) -> PyOutcomeDistribution {
+        PyOutcomeDistribution(self.0.distribution.clone())
+    }
+
+    #[getter]
+    fn expected_score(&self) -> f32 {
+        self.0.expected_score
+    }
+
+    /// Draws the option was sampled over, or `None` when it was counted exactly.
+    #[getter]
+    fn samples(&self) -> Option<u64> {
+        self.0.samples
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DiscardOption(discard='{}', expected_score={})",
+            Hand(self.0.discard.clone()),
+            self.0.expected_score
+        )
+    }
+}
+
+/// A sampled value with its 95% confidence interval.
+#[pyclass(name = "Estimate", module = "balatro_py", frozen)]
+pub struct PyEstimate(Estimate);
+
+#[pymethods]
+impl PyEstimate {
+    #[getter]
+    fn mean(&self) -> f64 {
+        self.0.mean
+    }
+
+    #[getter]
+    fn lower(&self) -> f64 {
+        self.0.lower
+    }
+
+    #[getter]
+    fn upper(&self) -> f64 {
+        self.0.upper
+    }
+
+    #[getter]
+    fn samples(&self) -> u64 {
+        self.0.samples
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Estimate(mean={}, lower={}, upper={})", self.0.mean, self.0.lower, self.0.upper)
+    }
+}
+
+/// Sampled outcome of a discard, with a confidence interval on every number.
+#[pyclass(name = "SampledOutcome", module = "balatro_py", frozen)]
+pub struct PySampledOutcome(SampledOutcome);
+
+#[pymethods]
+impl PySampledOutcome {
+    #[getter]
+    fn distribution(&self) -> PyOutcomeDistribution {
+        PyOutcomeDistribution(self.0.distribution.clone())
+    }
+
+    /// Estimate of every hand type's probability by name.
+    #[getter]
+    fn probabilities(&self) -> BTreeMap<String, PyEstimate> {
+        self.0.probabilities.iter().map(|(&rank, &estimate)| (name(rank), PyEstimate(estimate))).collect()
+    }
+
+    #[getter]
+    fn expected_score(&self) -> PyEstimate {
+        PyEstimate(self.0.expected_score)
+    }
+
+    #[getter]
+    fn samples(&self) -> u64 {
+        self.0.samples
+    }
+}
+
+/// Exact predictions against a deck, the standard deck by default, under a rule set.
+#[pyclass(name = "Predictor", module = "balatro_py", frozen)]
+pub struct PyPredictor {
+    deck: Deck,
+    rules: RuleSet,
+}
+
+impl PyPredictor {
+    fn predictor(&self, hand: Vec<CardData>) -> ExactPredictor {
+        ExactPredictor::new(hand, self.deck.clone(), self.rules.clone())
+    }
+
+    fn distribution_of(&self, hand: Vec<CardData>, discarded: &[CardData], discard: &[CardData]) -> PyResult<OutcomeDistribution> {
+        self.predictor(hand)
+            .calculate_outcome_distribution(optional(discarded), optional(discard))
+            .map_err(PredictorError::from_core)
+    }
+}
+
+#[pymethods]
+impl PyPredictor {
+    #[new]
+    #[pyo3(signature = (deck = None, rules = None))]
+    fn new(deck: Option<&Bound<'_, PyAny>>, rules: Option<&str>) -> PyResult<Self> {
+        Ok(PyPredictor {
+            deck: self::deck(deck)?,
+            rules: self::rules(rules)?,
+        })
+    }
+
+    /// Probability of every hand type after throwing away `discard` from `hand` and drawing
+    /// back up to a full hand. `discarded` holds the cards thrown away earlier in the round.
+    #[pyo3(signature = (hand, discarded = None, discard = None))]
+    fn distribution(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+        discard: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyOutcomeDistribution> {
+        let (hand, discarded, discard) = (self::hand(hand)?, optional_hand(discarded)?, optional_hand(discard)?);
+        py.detach(|| self.distribution_of(hand, &discarded, &discard)).map(PyOutcomeDistribution)
+    }
+
+    #[pyo3(signature = (hand, rank, discarded = None, discard = None))]
+    fn probability(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        rank: &str,
+        discarded: Option<&Bound<'_, PyAny>>,
+        discard: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<f32> {
+        let rank = named(&HandRank::ALL, rank, "hand type")?;
+        Ok(self.distribution(py, hand, discarded, discard)?.0.probability(rank))
+    }
+
+    /// Expected chips × mult score of the finished hand, including what `joker` adds.
+    #[pyo3(signature = (hand, discarded = None, discard = None, joker = None))]
+    fn expected_score(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+        discard: Option<&Bound<'_, PyAny>>,
+        joker: Option<&str>,
+    ) -> PyResult<f32> {
+        let (hand, discarded, discard, joker) =
+            (self::hand(hand)?, optional_hand(discarded)?, optional_hand(discard)?, self::joker(joker)?);
+        py.detach(|| {
+            self.predictor(hand)
+                .calculate_expected_score(optional(&discarded), optional(&discard), joker)
+                .map_err(PredictorError::from_core)
+        })
+    }
+
+    /// Every choice of cards to throw away from `hand`, best expected score first. With
+    /// `discards_remaining` above 1 the later discards are assumed to be played optimally,
+    /// and with 0 standing on the hand is the only option.
+    #[pyo3(signature = (hand, discarded = None, joker = None, discards_remaining = 1))]
+    fn optimize_discard(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+        joker: Option<&str>,
+        discards_remaining: u8,
+    ) -> PyResult<Vec<PyDiscardOption>> {
+        let (hand, discarded, joker) = (self::hand(hand)?, optional_hand(discarded)?, self::joker(joker)?);
+        let options = py.detach(|| {
+            let mut predictor = self.predictor(hand);
+            match discards_remaining {
+                1 => predictor.optimize_discard(optional(&discarded), joker),
+                _ => predictor.plan_discards(optional(&discarded), discards_remaining, joker),
+            }
+            .map_err(PredictorError::from_core)
+        })?;
+        Ok(options.into_iter().map(PyDiscardOption).collect())
+    }
+
+    /// The distribution of every hand in `hands`, drawing each back up to a full hand.
+    #[pyo3(signature = (hands, discarded = None))]
+    fn distributions(
+        &self,
+        py: Python<'_>,
+        hands: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<PyOutcomeDistribution>> {
+        let hands: Vec<Vec<CardData>> = hands.try_iter()?.map(|item| hand(&item?)).collect::<PyResult<_>>()?;
+        let discarded = optional_hand(discarded)?;
+        let distributions = py.detach(|| {
+            hands
+                .into_iter()
+                .map(|hand| self.distribution_of(hand, &discarded, &[]))
+                .collect::<PyResult<Vec<_>>>()
+        })?;
+        Ok(distributions.into_iter().map(PyOutcomeDistribution).collect())
+    }
+
+    /// Like `distributions`, as one `array.array` of doubles with a row of `HAND_RANKS`
+    /// probabilities per hand, for `numpy.asarray(...).reshape(-1, len(HAND_RANKS))`.
+    #[pyo3(signature = (hands, discarded = None))]
+    fn probability_matrix<'py>(
+        &self,
+        py: Python<'py>,
+        hands: &Bound<'py, PyAny>,
+        discarded: Option<&Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let values = self
+            .distributions(py, hands, discarded)?
+            .iter()
+            .flat_map(|distribution| HandRank::ALL.map(|rank| distribution.0.probability(rank) as f64))
+            .collect();
+        array(py, values)
+    }
+}
+
+/// Monte Carlo predictions, for hands and decks too large to enumerate.
+#[pyclass(name = "Simulator", module = "balatro_py", frozen)]
+pub struct PySimulator {
+    deck: Deck,
+    rules: RuleSet,
+    config: MonteCarloConfig,
+}
+
+#[pymethods]
+impl PySimulator {
+    /// Sampling stops after `max_samples` draws, or once every hand type's 95% interval is
+    /// narrower than `tolerance` on either side. A random seed is picked when `seed` is None.
+    #[new]
+    #[pyo3(signature = (deck = None, rules = None, seed = None, max_samples = 100_000, tolerance = None, inner_samples = 64))]
+    fn new(
+        deck: Option<&Bound<'_, PyAny>>,
+        rules: Option<&str>,
+        seed: Option<u64>,
+        max_samples: u64,
+        tolerance: Option<f64>,
+        inner_samples: u32,
+    ) -> PyResult<Self> {
+        Ok(PySimulator {
+            deck: self::deck(deck)?,
+            rules: self::rules(rules)?,
+            config: MonteCarloConfig {
+                seed,
+                max_samples,
+                tolerance,
+                inner_samples,
+            },
+        })
+    }
+
+    /// Throws away `discard` from `hand`, then plays out random draws until the hand locks,
+    /// choosing each of the `discards_after` later discards by sampling.
+    #[pyo3(signature = (hand, discarded = None, discard = None, discards_after = 0, joker = None))]
+    fn simulate(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+        discard: Option<&Bound<'_, PyAny>>,
+        discards_after: u8,
+        joker: Option<&str>,
+    ) -> PyResult<PySampledOutcome> {
+        let (hand, discarded, discard, joker) =
+            (self::hand(hand)?, optional_hand(discarded)?, optional_hand(discard)?, self::joker(joker)?);
+        py.detach(|| {
+            monte_carlo::simulate(
+                &hand,
+                &discarded,
+                &discard,
+                discards_after,
+                &self.deck,
+                joker,
+                &self.config,
+                &self.rules,
+            )
+            .map_err(PredictorError::from_core)
+        })
+        .map(PySampledOutcome)
+    }
+
+    /// Every first discard from `hand`, best sampled score first.
+    #[pyo3(signature = (hand, discarded = None, joker = None, discards_remaining = 1))]
+    fn optimize_discard(
+        &self,
+        py: Python<'_>,
+        hand: &Bound<'_, PyAny>,
+        discarded: Option<&Bound<'_, PyAny>>,
+        joker: Option<&str>,
+        discards_remaining: u8,
+    ) -> PyResult<Vec<PyDiscardOption>> {
+        let (hand, discarded, joker) = (self::hand(hand)?, optional_hand(discarded)?, self::joker(joker)?);
+        let options = py.detach(|| {
+            monte_carlo::simulate_plan(&hand, &discarded, discards_remaining, &self.deck, joker, &self.config, &self.rules)
+                .map_err(PredictorError::from_core)
+        })?;
+        Ok(options.into_iter().map(PyDiscardOption).collect())
+    }
+}
+
+/// The best hand in `hand` and its score, including what `joker` adds.
+#[pyfunction]
+#[pyo3(signature = (hand, joker = None, rules = None))]
+fn evaluate(hand: &Bound<'_, PyAny>, joker: Option<&str>, rules: Option<&str>) -> PyResult<PyHandEvaluation> {
+    let (hand, joker, rules) = (self::hand(hand)?, self::joker(joker)?, self::rules(rules)?);
+    let evaluation = evaluator::evaluate_hand_with(&hand, &rules);
+    let round = RoundContext {
+        starting_hand: &hand,
+        stages: &[],
+        final_hand: &hand,
+    };
+    let score = scoring::score_round(&evaluation, &round, joker, &rules);
+    Ok(PyHandEvaluation {
+        rank: name(evaluation.rank),
+        scoring_cards: cards(&evaluation.scoring_cards),
+        kickers: cards(&evaluation.kickers),
+        key: evaluation.key,
+        chips: score.chips,
+        mult: score.mult,
+        score: score.total,
+    })
+}
+
+/// Reads a hand such as `"Ah Kd 10c"` into a list of cards.
+#[pyfunction]
+fn parse_hand(text: &str) -> PyResult<Vec<PyCard>> {
+    let hand: Hand = text.parse().map_err(value_error)?;
+    Ok(cards(&hand))
+}
+
+/// Hand evaluation, outcome distributions, discard advice and simulation for the DST card
+/// game and Balatro.
+#[pymodule]
+pub mod balatro_py {
+    use pyo3::prelude::*;
+
+    #[pymodule_export]
+    use super::{
+        evaluate, parse_hand, PredictorError, PyCard, PyDiscardOption, PyEstimate, PyHandEvaluation,
+        PyOutcomeDistribution, PyPredictor, PySampledOutcome, PySimulator,
+    };
+
+    #[pymodule_init]
+    fn init(module: &Bound<'_, PyModule>) -> PyResult<()> {
+        use balatro_core::hand_rank::HandRank;
+        let names: Vec<String> = HandRank::ALL.iter().map(|&rank| super::name(rank)).collect();
+        module.add("HAND_RANKS", names)?;
+        let names: Vec<String> = balatro_core::joker::Joker::ALL.iter().map(|&joker| super::name(joker)).collect();
+        module.add("JOKERS", names)
+    }
+}
@@ -0,0 +1,103 @@
+mod test {
+    use balatro_py::balatro_py;
+    use pyo3::prelude::*;
+    use std::ffi::CStr;
+    use std::sync::Once;
+
+    /// Runs `code` in an interpreter where `balatro_py` can be imported.
+    fn run(code: &CStr) {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            pyo3::append_to_inittab!(balatro_py);
+            Python::initialize();
+        });
+        Python::attach(|py| {
+            if let Err(error) = py.run(code, None, None) {
+                error.display(py);
+                panic!("{}", error);
+            }
+        });
+    }
+
+    #[test]
+    fn test_cards_and_hands() {
+        run(c"
+import balatro_py as b
+
+ace = b.CardData('Ace', 'Hearts')
+assert ace == b.CardData.parse('Ah') == b.CardData(14, 'h')
+assert (ace.face, ace.suit, ace.rank, str(ace)) == ('Ace', 'Hearts', 14, 'Ah')
+assert b.CardData.from_id(ace.id) == ace
+assert len({ace, b.CardData.parse('Ah'), b.CardData.parse('As')}) == 2
+
+flush = b.evaluate('2h 7h 9h Kh Ah')
+assert flush.rank == 'Flush'
+assert flush.score == flush.chips * flush.mult
+for hand in (['2h', '7h', '9h', 'Kh', 'Ah'], [('2', 'h'), (7, 'Hearts'), ('9', 'H'), ('K', 'h'), ace]):
+    assert b.evaluate(hand).score == flush.score
+assert b.parse_hand('Ah 10s') == [ace, b.CardData('10', 'Spades')]
+
+try:
+    b.evaluate('Zz')
+    assert False
+except ValueError:
+    pass
+");
+    }
+
+    #[test]
+    fn test_predictor() {
+        run(c"
+import balatro_py as b
+
+predictor = b.Predictor()
+# Four hearts, drawing one card for the flush
+distribution = predictor.distribution('2h 7h 9h Kh')
+assert distribution.draws == 48
+assert abs(distribution['Flush'] - 9 / 48) < 1e-6
+assert abs(predictor.probability('2h 7h 9h Kh', 'flush') - 9 / 48) < 1e-6
+assert abs(sum(distribution.probabilities.values()) - 1) < 1e-4
+assert list(distribution.to_array()) == [distribution[rank] for rank in b.HAND_RANKS]
+
+matrix = predictor.probability_matrix(['2h 7h 9h Kh', '2c 2d 9s Kh'])
+assert len(matrix) == 2 * len(b.HAND_RANKS)
+assert memoryview(matrix).format == 'd'
+assert matrix[b.HAND_RANKS.index('Flush')] == distribution['Flush']
+
+options = predictor.optimize_discard('2h 7h 9h Kh Ks', discarded=['3c'])
+assert len(options) == 32
+assert all(a.expected_score >= b.expected_score for a, b in zip(options, options[1:]))
+assert sorted(map(str, options[0].kept + options[0].discard)) == sorted('2h 7h 9h Kh Ks'.split())
+assert options[0].samples is None
+
+# With no discards left, standing is the only option
+options = predictor.optimize_discard('2h 7h 9h Kh Ks', discarded=['3c'], discards_remaining=0)
+assert len(options) == 1 and options[0].discard == []
+
+try:
+    predictor.distribution('As As')
+    assert False
+except b.PredictorError as error:
+    assert error.args[1] == 'duplicate_card'
+");
+    }
+
+    #[test]
+    fn test_simulator() {
+        run(c"
+import balatro_py as b
+
+simulator = b.Simulator(seed=7, max_samples=20000)
+outcome = simulator.simulate('2h 7h 9h Kh Ks', discard=['Ks'])
+assert outcome.samples == 20000
+flush = outcome.probabilities['Flush']
+assert flush.lower <= 9 / 47 <= flush.upper
+assert outcome.expected_score.mean > 0
+
+# Without the clubs and diamonds every draw is a heart or a spade
+deck = [card for card in (b.CardData.from_id(id) for id in range(52)) if card.suit in ('Hearts', 'Spades')]
+options = b.Simulator(deck=deck, seed=7, max_samples=2000).optimize_discard('2h 7h 9h Kh Ks')
+assert len(options) == 32
+");
+    }
+}
@@ -0,0 +1,250 @@
+//! WebAssembly exports of the predictor, for running the frontend in a browser.
+//!
+//! Every export takes the same arguments object the frontend passes to Tauri's `invoke()`
+//! and returns, or throws, the same values the Tauri command does. [`invoke`] dispatches on
+//! the command name, so it can stand in for `invoke()` from `@tauri-apps/api/core`.
+
+use balatro_core::balatro_predictor::PredictionBackend;
+use balatro_core::card_data::{BalatroPredictor, CardData};
+use balatro_core::deck::{Deck, DeckChange};
+use balatro_core::error::PredictorError;
+use balatro_core::evaluator;
+use balatro_core::hand_rank::HandRank;
+use balatro_core::joker::{Joker, RoundContext};
+use balatro_core::monte_carlo::{self, MonteCarloConfig};
+use balatro_core::notation::Hand;
+use balatro_core::rules::RuleSet;
+use balatro_core::scoring;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Arguments of the prediction commands. Anything left out takes the same default as in
+/// the Tauri commands.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictArgs {
+    pub hand: Vec<CardData>,
+    #[serde(default)]
+    pub discarded_cards: Vec<CardData>,
+    #[serde(default)]
+    pub discard: Vec<CardData>,
+    pub joker: Option<Joker>,
+    pub backend: Option<PredictionBackend>,
+    pub deck: Option<Deck>,
+    pub rules: Option<RuleSet>,
+    /// Discards left this round, counting the one being chosen. Only used by `plan_discards`.
+    #[serde(default)]
+    pub discards_remaining: u8,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbabilityArgs {
+    pub rank: HandRank,
+    #[serde(flatten)]
+    pub predict: PredictArgs,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateArgs {
+    pub hand: Vec<CardData>,
+    #[serde(default)]
+    pub discarded_cards: Vec<CardData>,
+    #[serde(default)]
+    pub discard: Vec<CardData>,
+    #[serde(default)]
+    pub discards_after: u8,
+    pub joker: Option<Joker>,
+    pub config: Option<MonteCarloConfig>,
+    pub deck: Option<Deck>,
+    pub rules: Option<RuleSet>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct HandArgs {
+    pub hand: Vec<CardData>,
+    pub joker: Option<Joker>,
+    pub rules: Option<RuleSet>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct ParseArgs {
+    pub text: String,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct DeckArgs {
+    pub changes: Vec<DeckChange>,
+}
+
+impl PredictArgs {
+    fn optional(cards: &[CardData]) -> Option<Vec<CardData>> {
+        if cards.is_empty() { None } else { Some(cards.to_vec()) }
+    }
+
+    fn predictor(&self) -> Box<dyn BalatroPredictor> {
+        self.backend.clone().unwrap_or_default().predictor(
+            self.hand.clone(),
+            self.deck.clone().unwrap_or_default(),
+            self.rules.clone().unwrap_or_default(),
+        )
+    }
+}
+
+/// Reads a command's arguments object.
+fn from_js<T: DeserializeOwned>(args: JsValue) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(args).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Converts a result to plain JS objects and arrays, the same shape Tauri hands back.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Throws a predictor error as `{code, message, cards}`, like the Tauri commands reject with.
+fn reject(error: impl Into<PredictorError>) -> JsValue {
+    to_js(&error.into()).unwrap_or_else(|e| e)
+}
+
+/// Runs the command named `command` with `args`, in place of Tauri's `invoke()`.
+#[wasm_bindgen]
+pub fn invoke(command: &str, args: JsValue) -> Result<JsValue, JsValue> {
+    match command {
+        "evaluate_hand" => evaluate_hand(args),
+        "parse_hand" => parse_hand(args),
+        "score_hand" => score_hand(args),
+        "calculate_probability" => calculate_probability(args),
+        "calculate_expected_score" => calculate_expected_score(args),
+        "calculate_outcome_distribution" => calculate_outcome_distribution(args),
+        "optimize_discard" => optimize_discard(args),
+        "plan_discards" => plan_discards(args),
+        "simulate_outcome" => simulate_outcome(args),
+        "build_deck" => build_deck(args),
+        "rule_set_presets" => rule_set_presets(),
+        _ => Err(JsValue::from_str(&format!("Command {} is not available in the browser", command))),
+    }
+}
+
+#[wasm_bindgen]
+pub fn evaluate_hand(args: JsValue) -> Result<JsValue, JsValue> {
+    let HandArgs { hand, rules, .. } = from_js(args)?;
+    to_js(&evaluator::evaluate_hand_with(&hand, &rules.unwrap_or_default()))
+}
+
+#[wasm_bindgen]
+pub fn parse_hand(args: JsValue) -> Result<JsValue, JsValue> {
+    let ParseArgs { text } = from_js(args)?;
+    let hand = text.parse::<Hand>().map_err(|e| JsValue::from_str(&e.to_string()))?;
+    to_js(&Vec::from(hand))
+}
+
+#[wasm_bindgen]
+pub fn score_hand(args: JsValue) -> Result<JsValue, JsValue> {
+    let HandArgs { hand, joker, rules } = from_js(args)?;
+    let rules = rules.unwrap_or_default();
+    let round = RoundContext {
+        starting_hand: &hand,
+        stages: &[],
+        final_hand: &hand,
+    };
+    to_js(&scoring::score_round(&evaluator::evaluate_hand_with(&hand, &rules), &round, joker, &rules))
+}
+
+/// Probability that the hand finishes as `rank`, which the Tauri app reads off the
+/// outcome distribution instead.
+#[wasm_bindgen]
+pub fn calculate_probability(args: JsValue) -> Result<JsValue, JsValue> {
+    let ProbabilityArgs { rank, predict } = from_js(args)?;
+    let distribution = predict
+        .predictor()
+        .calculate_outcome_distribution(
+            PredictArgs::optional(&predict.discarded_cards),
+            PredictArgs::optional(&predict.discard),
+        )
+        .map_err(reject)?;
+    to_js(&distribution.probability(rank))
+}
+
+#[wasm_bindgen]
+pub fn calculate_expected_score(args: JsValue) -> Result<JsValue, JsValue> {
+    let args: PredictArgs = from_js(args)?;
+    let expected_score = args
+        .predictor()
+        .calculate_expected_score(
+            PredictArgs::optional(&args.discarded_cards),
+            PredictArgs::optional(&args.discard),
+            args.joker,
+        )
+        .map_err(reject)?;
+    to_js(&expected_score)
+}
+
+#[wasm_bindgen]
+pub fn calculate_outcome_distribution(args: JsValue) -> Result<JsValue, JsValue> {
+    let args: PredictArgs = from_js(args)?;
+    let distribution = args
+        .predictor()
+        .calculate_outcome_distribution(
+            PredictArgs::optional(&args.discarded_cards),
+            PredictArgs::optional(&args.discard),
+        )
+        .map_err(reject)?;
+    to_js(&distribution)
+}
+
+#[wasm_bindgen]
+pub fn optimize_discard(args: JsValue) -> Result<JsValue, JsValue> {
+    let args: PredictArgs = from_js(args)?;
+    let options = args
+        .predictor()
+        .optimize_discard(PredictArgs::optional(&args.discarded_cards), args.joker)
+        .map_err(reject)?;
+    to_js(&options)
+}
+
+#[wasm_bindgen]
+pub fn plan_discards(args: JsValue) -> Result<JsValue, JsValue> {
+    let args: PredictArgs = from_js(args)?;
+    let options = args
+        .predictor()
+        .plan_discards(
+            PredictArgs::optional(&args.discarded_cards),
+            args.discards_remaining,
+            args.joker,
+        )
+        .map_err(reject)?;
+    to_js(&options)
+}
+
+#[wasm_bindgen]
+pub fn simulate_outcome(args: JsValue) -> Result<JsValue, JsValue> {
+    let args: SimulateArgs = from_js(args)?;
+    let outcome = monte_carlo::simulate(
+        &args.hand,
+        &args.discarded_cards,
+        &args.discard,
+        args.discards_after,
+        &args.deck.unwrap_or_default(),
+        args.joker,
+        &args.config.unwrap_or_default(),
+        &args.rules.unwrap_or_default(),
+    )
+    .map_err(reject)?;
+    to_js(&outcome)
+}
+
+#[wasm_bindgen]
+pub fn build_deck(args: JsValue) -> Result<JsValue, JsValue> {
+    let DeckArgs { changes } = from_js(args)?;
+    to_js(&Deck::from_changes(&changes))
+}
+
+#[wasm_bindgen]
+pub fn rule_set_presets() -> Result<JsValue, JsValue> {
+    let presets: Vec<(&str, &RuleSet)> = RuleSet::PRESETS.iter().map(|(name, rules)| (*name, rules)).collect();
+    to_js(&presets)
+}
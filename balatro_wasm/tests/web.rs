@@ -0,0 +1,62 @@
+//! Run with `wasm-pack test --node balatro_wasm`.
+#![cfg(target_arch = "wasm32")]
+
+mod test {
+    use balatro_wasm::invoke;
+    use serde::Serialize;
+    use serde_json::{json, Value};
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn js(value: Value) -> JsValue {
+        value.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap()
+    }
+
+    fn call(command: &str, args: Value) -> Result<Value, Value> {
+        invoke(command, js(args))
+            .map(|value| serde_wasm_bindgen::from_value(value).unwrap())
+            .map_err(|error| serde_wasm_bindgen::from_value(error).unwrap())
+    }
+
+    fn card(face: &str, suit: &str) -> Value {
+        json!({ "card_face": face, "card_suit": suit })
+    }
+
+    #[wasm_bindgen_test]
+    fn test_predictions() {
+        // Four hearts, drawing one card for the flush
+        let hand = json!([card("Two", "Hearts"), card("Seven", "Hearts"), card("Nine", "Hearts"), card("King", "Hearts")]);
+        let flush = call("calculate_probability", json!({ "hand": hand, "rank": "Flush" })).unwrap();
+        let flush = flush.as_f64().unwrap();
+        assert!((flush - 9.0 / 48.0).abs() < 1e-6);
+
+        let distribution = call("calculate_outcome_distribution", json!({ "hand": hand })).unwrap();
+        assert_eq!(distribution["draws"], 48);
+        assert_eq!(distribution["probabilities"]["Flush"].as_f64().unwrap(), flush);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_discard_advice() {
+        let args = json!({ "hand": ["2h", "7h", "9h", "Kh", "Ks"], "discardedCards": ["3c"] });
+        let options = call("optimize_discard", args.clone()).unwrap();
+        let options = options.as_array().unwrap();
+        assert_eq!(options.len(), 32);
+        let scores: Vec<f64> = options.iter().map(|option| option["expected_score"].as_f64().unwrap()).collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let mut args = args;
+        args["discardsRemaining"] = json!(2);
+        args["backend"] = json!({ "type": "MonteCarlo", "seed": 7, "max_samples": 2000, "inner_samples": 20 });
+        assert_eq!(call("plan_discards", args).unwrap().as_array().unwrap().len(), 32);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_errors() {
+        let error = call("calculate_outcome_distribution", json!({ "hand": ["As", "As"] })).unwrap_err();
+        assert_eq!(error["code"], "duplicate_card");
+        assert_eq!(error["cards"], json!([card("Ace", "Spades")]));
+
+        assert!(call("parse_hand", json!({ "text": "Zz" })).is_err());
+        assert!(call("end_round", json!({})).is_err());
+    }
+}
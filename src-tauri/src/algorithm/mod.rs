@@ -1,5 +0,0 @@
-pub mod card_data;
-pub mod card_suits;
-pub mod balatro_predictor;
-mod faces;
-pub mod deck;
\ No newline at end of file
@@ -0,0 +1,247 @@
+use crate::algorithm::calibration::PredictionOutcome;
+use crate::algorithm::card_data::CardData;
+use crate::algorithm::evaluator::evaluate_hand_with;
+use crate::algorithm::hand_rank::HandRank;
+use crate::algorithm::joker::{DiscardStage, Joker};
+use crate::algorithm::scoring::Score;
+use crate::algorithm::session::{RoundSession, ShownPredictions};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, Row};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A finished round as it is kept in the history.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RoundRecord {
+    /// Row id in the history, `None` until the round has been recorded.
+    pub id: Option<i64>,
+    /// When the round ended, in seconds since the Unix epoch.
+    pub played_at: i64,
+    pub joker: Option<Joker>,
+    pub starting_hand: Vec<CardData>,
+    /// The discards chosen and the cards drawn for each of them.
+    pub stages: Vec<DiscardStage>,
+    pub final_hand: Vec<CardData>,
+    pub rank: HandRank,
+    pub score: Score,
+    /// The discard options that were shown before each discard.
+    pub predictions: Vec<ShownPredictions>,
+}
+
+impl RoundRecord {
+    /// The record of playing the current hand of `session` now.
+    pub fn from_session(session: &RoundSession) -> Self {
+        RoundRecord {
+            id: None,
+            played_at: now(),
+            joker: session.joker,
+            starting_hand: session.starting_hand.clone(),
+            stages: session.stages.clone(),
+            final_hand: session.hand.clone(),
+            rank: evaluate_hand_with(&session.hand, &session.rules).rank,
+            score: session.score(),
+            predictions: session.predictions.clone(),
+        }
+    }
+}
+
+/// Which rounds to list. Every field left out matches all rounds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct HistoryFilter {
+    pub joker: Option<Joker>,
+    pub rank: Option<HandRank>,
+    /// Earliest end of a round, in seconds since the Unix epoch.
+    pub from: Option<i64>,
+    /// Latest end of a round, in seconds since the Unix epoch.
+    pub to: Option<i64>,
+}
+
+/// Every finished round, kept in a SQLite database.
+pub struct History {
+    connection: Mutex<Connection>,
+}
+
+impl History {
+    /// Opens the history at `path`, creating the database the first time.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        History::with_connection(Connection::open(path)?)
+    }
+
+    /// A history that only lives as long as it does.
+    pub fn in_memory() -> anyhow::Result<Self> {
+        History::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> anyhow::Result<Self> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS rounds (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                played_at INTEGER NOT NULL,
+                joker TEXT,
+                rank TEXT NOT NULL,
+                chips INTEGER NOT NULL,
+                mult REAL NOT NULL,
+                total INTEGER NOT NULL,
+                starting_hand TEXT NOT NULL,
+                stages TEXT NOT NULL,
+                final_hand TEXT NOT NULL,
+                predictions TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS rounds_played_at ON rounds (played_at);
+            CREATE TABLE IF NOT EXISTS prediction_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                round_id INTEGER NOT NULL,
+                distribution TEXT NOT NULL,
+                actual TEXT NOT NULL
+            );",
+        )?;
+        Ok(History {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Stores `round` and returns its id, logging `outcomes` with it for calibration.
+    pub fn record(&self, round: &RoundRecord, outcomes: &[PredictionOutcome]) -> anyhow::Result<i64> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO rounds (played_at, joker, rank, chips, mult, total, starting_hand, stages, final_hand, predictions)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                round.played_at,
+                round.joker.map(|joker| name(&joker)).transpose()?,
+                name(&round.rank)?,
+                round.score.chips,
+                round.score.mult,
+                round.score.total,
+                serde_json::to_string(&round.starting_hand)?,
+                serde_json::to_string(&round.stages)?,
+                serde_json::to_string(&round.final_hand)?,
+                serde_json::to_string(&round.predictions)?,
+            ],
+        )?;
+        let id = transaction.last_insert_rowid();
+        for outcome in outcomes {
+            transaction.execute(
+                "INSERT INTO prediction_log (round_id, distribution, actual) VALUES (?1, ?2, ?3)",
+                params![id, serde_json::to_string(&outcome.distribution)?, name(&outcome.actual)?],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    /// Every logged prediction next to the hand that actually happened, oldest first.
+    pub fn prediction_log(&self) -> anyhow::Result<Vec<PredictionOutcome>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare("SELECT distribution, actual FROM prediction_log ORDER BY id")?;
+        let mut rows = statement.query([])?;
+        let mut log = Vec::new();
+        while let Some(row) = rows.next()? {
+            log.push(PredictionOutcome {
+                distribution: serde_json::from_str(&row.get::<_, String>(0)?)?,
+                actual: parse_name(&row.get::<_, String>(1)?)?,
+            });
+        }
+        Ok(log)
+    }
+
+    /// Every round matching `filter`, most recent first.
+    pub fn list(&self, filter: &HistoryFilter) -> anyhow::Result<Vec<RoundRecord>> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(joker) = filter.joker {
+            conditions.push("joker = ?");
+            values.push(Value::Text(name(&joker)?));
+        }
+        if let Some(rank) = filter.rank {
+            conditions.push("rank = ?");
+            values.push(Value::Text(name(&rank)?));
+        }
+        if let Some(from) = filter.from {
+            conditions.push("played_at >= ?");
+            values.push(Value::Integer(from));
+        }
+        if let Some(to) = filter.to {
+            conditions.push("played_at <= ?");
+            values.push(Value::Integer(to));
+        }
+
+        let mut query = String::from(
+            "SELECT id, played_at, joker, rank, chips, mult, total, starting_hand, stages, final_hand, predictions
+            FROM rounds",
+        );
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&conditions.join(" AND "));
+        }
+        query.push_str(" ORDER BY played_at DESC, id DESC");
+
+        let connection = self.connection();
+        let mut statement = connection.prepare(&query)?;
+        let mut rows = statement.query(params_from_iter(values))?;
+        let mut rounds = Vec::new();
+        while let Some(row) = rows.next()? {
+            rounds.push(read_round(row)?);
+        }
+        Ok(rounds)
+    }
+
+    /// Removes the round with `id` and its logged predictions, returning whether there was one.
+    pub fn delete(&self, id: i64) -> anyhow::Result<bool> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute("DELETE FROM prediction_log WHERE round_id = ?1", [id])?;
+        let deleted = transaction.execute("DELETE FROM rounds WHERE id = ?1", [id])? > 0;
+        transaction.commit()?;
+        Ok(deleted)
+    }
+}
+
+fn read_round(row: &Row) -> anyhow::Result<RoundRecord> {
+    let joker: Option<String> = row.get(2)?;
+    Ok(RoundRecord {
+        id: Some(row.get(0)?),
+        played_at: row.get(1)?,
+        joker: joker.map(|joker| parse_name(&joker)).transpose()?,
+        rank: parse_name(&row.get::<_, String>(3)?)?,
+        score: Score {
+            chips: row.get(4)?,
+            mult: row.get(5)?,
+            total: row.get(6)?,
+        },
+        starting_hand: serde_json::from_str(&row.get::<_, String>(7)?)?,
+        stages: serde_json::from_str(&row.get::<_, String>(8)?)?,
+        final_hand: serde_json::from_str(&row.get::<_, String>(9)?)?,
+        predictions: serde_json::from_str(&row.get::<_, String>(10)?)?,
+    })
+}
+
+/// The name serde gives a unit variant such as a joker or a hand type, which is what the
+/// database stores so the rows stay readable.
+fn name(value: &impl Serialize) -> anyhow::Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => Err(anyhow::anyhow!("Expected a name but got {}", other)),
+    }
+}
+
+fn parse_name<T: DeserializeOwned>(name: &str) -> anyhow::Result<T> {
+    Ok(serde_json::from_value(serde_json::Value::String(name.to_string()))?)
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
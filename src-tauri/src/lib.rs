@@ -1,183 +1,319 @@
-use crate::algorithm::card_data::{BalatroPredictor, CardData};
+use crate::algorithm::balatro_predictor::PredictionBackend;
+use crate::algorithm::calibration::CalibrationReport;
+use crate::algorithm::card_data::CardData;
+use crate::algorithm::deck::{Deck, DeckChange};
+use crate::algorithm::distribution::OutcomeDistribution;
+use crate::algorithm::error::PredictorError;
+use crate::algorithm::evaluator::{self, HandEvaluation};
+use crate::algorithm::joker::{Joker, RoundContext};
+use crate::algorithm::monte_carlo::{self, MonteCarloConfig, SampledOutcome};
+use crate::algorithm::notation::Hand;
+use crate::algorithm::optimizer::DiscardOption;
+use crate::algorithm::rules::RuleSet;
+use crate::algorithm::scoring::{self, Score};
+use crate::algorithm::session::RoundSession;
+use crate::history::{History, HistoryFilter, RoundRecord};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tauri::Manager;
 
-pub mod algorithm;
+pub use balatro_core as algorithm;
+pub mod history;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(CurrentRound::default())
+        .setup(|app| {
+            let directory = app.path().app_data_dir()?;
+            std::fs::create_dir_all(&directory)?;
+            app.manage(History::open(directory.join("history.sqlite3"))?);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
-            calculate_high_card_probability,
-            calculate_pair_probability,
-            calculate_two_pair_probability,
-            calculate_three_of_a_kind_probability,
-            calculate_straight_probability,
-            calculate_flush_probability,
-            calculate_full_house_probability,
-            calculate_four_of_a_kind_probability,
-            calculate_straight_flush_probability,
-            calculate_royal_flush_probability
+            calculate_expected_score,
+            calculate_outcome_distribution,
+            evaluate_hand,
+            parse_hand,
+            score_hand,
+            optimize_discard,
+            plan_discards,
+            simulate_outcome,
+            build_deck,
+            rule_set_presets,
+            load_rule_set,
+            start_round,
+            apply_discard,
+            current_predictions,
+            end_round,
+            list_rounds,
+            delete_round,
+            calibration_report,
+            calibration_csv
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
 #[tauri::command]
-fn calculate_high_card_probability(
-    hand: Vec<CardData>,
-    discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_high_card_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+fn evaluate_hand(hand: Vec<CardData>, rules: Option<RuleSet>) -> HandEvaluation {
+    evaluator::evaluate_hand_with(&hand, &rules.unwrap_or_default())
 }
 
 #[tauri::command]
-fn calculate_pair_probability(
-    hand: Vec<CardData>,
-    discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_pair_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+fn parse_hand(text: String) -> Result<Vec<CardData>, String> {
+    text.parse::<Hand>().map(Vec::from).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn calculate_two_pair_probability(
+fn score_hand(hand: Vec<CardData>, joker: Option<Joker>, rules: Option<RuleSet>) -> Score {
+    let rules = rules.unwrap_or_default();
+    let round = RoundContext {
+        starting_hand: &hand,
+        stages: &[],
+        final_hand: &hand,
+    };
+    scoring::score_round(&evaluator::evaluate_hand_with(&hand, &rules), &round, joker, &rules)
+}
+
+#[tauri::command(async)]
+fn calculate_expected_score(
     hand: Vec<CardData>,
     discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_two_pair_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    discard: Vec<CardData>,
+    joker: Option<Joker>,
+    backend: Option<PredictionBackend>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<f32, PredictorError> {
+    let expected_score = backend
+        .unwrap_or_default()
+        .predictor(hand, deck.unwrap_or_default(), rules.unwrap_or_default())
+        .calculate_expected_score(
+            if discarded_cards.is_empty() {
+                None
+            } else {
+                Some(discarded_cards)
+            },
+            if discard.is_empty() { None } else { Some(discard) },
+            joker,
+        )?;
+    Ok(expected_score)
 }
 
-#[tauri::command]
-fn calculate_three_of_a_kind_probability(
+#[tauri::command(async)]
+fn calculate_outcome_distribution(
     hand: Vec<CardData>,
     discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_three_of_a_kind_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    discard: Vec<CardData>,
+    backend: Option<PredictionBackend>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<OutcomeDistribution, PredictorError> {
+    let distribution = backend
+        .unwrap_or_default()
+        .predictor(hand, deck.unwrap_or_default(), rules.unwrap_or_default())
+        .calculate_outcome_distribution(
+            if discarded_cards.is_empty() {
+                None
+            } else {
+                Some(discarded_cards)
+            },
+            if discard.is_empty() { None } else { Some(discard) },
+        )?;
+    Ok(distribution)
 }
 
-#[tauri::command]
-fn calculate_straight_probability(
+#[tauri::command(async)]
+fn optimize_discard(
     hand: Vec<CardData>,
     discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_straight_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    joker: Option<Joker>,
+    backend: Option<PredictionBackend>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<Vec<DiscardOption>, PredictorError> {
+    let options = backend
+        .unwrap_or_default()
+        .predictor(hand, deck.unwrap_or_default(), rules.unwrap_or_default())
+        .optimize_discard(
+            if discarded_cards.is_empty() {
+                None
+            } else {
+                Some(discarded_cards)
+            },
+            joker,
+        )?;
+    Ok(options)
 }
 
-#[tauri::command]
-fn calculate_flush_probability(
+#[tauri::command(async)]
+fn plan_discards(
     hand: Vec<CardData>,
     discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_flush_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    discards_remaining: u8,
+    joker: Option<Joker>,
+    backend: Option<PredictionBackend>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<Vec<DiscardOption>, PredictorError> {
+    let options = backend
+        .unwrap_or_default()
+        .predictor(hand, deck.unwrap_or_default(), rules.unwrap_or_default())
+        .plan_discards(
+            if discarded_cards.is_empty() {
+                None
+            } else {
+                Some(discarded_cards)
+            },
+            discards_remaining,
+            joker,
+        )?;
+    Ok(options)
 }
 
-#[tauri::command]
-fn calculate_full_house_probability(
+#[tauri::command(async)]
+#[allow(clippy::too_many_arguments)]
+fn simulate_outcome(
     hand: Vec<CardData>,
     discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_full_house_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    discard: Vec<CardData>,
+    discards_after: u8,
+    joker: Option<Joker>,
+    config: Option<MonteCarloConfig>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<SampledOutcome, PredictorError> {
+    monte_carlo::simulate(
+        &hand,
+        &discarded_cards,
+        &discard,
+        discards_after,
+        &deck.unwrap_or_default(),
+        joker,
+        &config.unwrap_or_default(),
+        &rules.unwrap_or_default(),
+    )
+    .map_err(PredictorError::from)
 }
 
 #[tauri::command]
-fn calculate_four_of_a_kind_probability(
-    hand: Vec<CardData>,
-    discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_four_of_a_kind_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+fn build_deck(changes: Vec<DeckChange>) -> Deck {
+    Deck::from_changes(&changes)
 }
 
 #[tauri::command]
-fn calculate_straight_flush_probability(
-    hand: Vec<CardData>,
-    discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_straight_flush_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+fn rule_set_presets() -> Vec<(String, RuleSet)> {
+    RuleSet::PRESETS
+        .iter()
+        .map(|(name, rules)| (name.to_string(), rules.clone()))
+        .collect()
+}
+
+/// Reads the `.toml` or `.json` rule set called `name` from the app's config folder.
+#[tauri::command]
+fn load_rule_set(app: tauri::AppHandle, name: String) -> Result<RuleSet, String> {
+    let directory = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    RuleSet::load(file_in(directory, &name)?).map_err(|e| e.to_string())
+}
+
+/// `name` inside `directory`, refusing anything but a plain file name so a command cannot
+/// reach outside the app's own folders.
+fn file_in(directory: PathBuf, name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(name);
+    match path.file_name() {
+        Some(file_name) if file_name == path.as_os_str() => Ok(directory.join(file_name)),
+        _ => Err(format!("{:?} is not a file name", name)),
+    }
+}
+
+/// The round being played, if one has been started.
+#[derive(Default)]
+struct CurrentRound(Mutex<Option<RoundSession>>);
+
+impl CurrentRound {
+    fn lock(&self) -> MutexGuard<'_, Option<RoundSession>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 #[tauri::command]
-fn calculate_royal_flush_probability(
+fn start_round(
+    state: tauri::State<'_, CurrentRound>,
     hand: Vec<CardData>,
-    discarded_cards: Vec<CardData>,
-) -> Result<f32, String> {
-    let mut hand = hand.to_vec().clone();
-    let probability = hand
-        .calculate_royal_flush_probability(if discarded_cards.is_empty() {
-            None
-        } else {
-            Some(discarded_cards)
-        })
-        .map_err(|e| e.to_string())?;
-    Ok(probability)
+    joker: Option<Joker>,
+    deck: Option<Deck>,
+    rules: Option<RuleSet>,
+) -> Result<RoundSession, PredictorError> {
+    let session = RoundSession::start(hand, joker, deck.unwrap_or_default(), rules.unwrap_or_default())?;
+    *state.lock() = Some(session.clone());
+    Ok(session)
+}
+
+#[tauri::command]
+fn apply_discard(
+    state: tauri::State<'_, CurrentRound>,
+    cards: Vec<CardData>,
+    drawn: Vec<CardData>,
+) -> Result<RoundSession, PredictorError> {
+    let mut current = state.lock();
+    let session = current.as_mut().ok_or(PredictorError::NoRound)?;
+    session.apply_discard(cards, drawn)?;
+    Ok(session.clone())
+}
+
+#[tauri::command(async)]
+fn current_predictions(
+    state: tauri::State<'_, CurrentRound>,
+    backend: Option<PredictionBackend>,
+) -> Result<Vec<DiscardOption>, PredictorError> {
+    // Work on a copy so the round stays available while the predictions run
+    let session = state.lock().clone().ok_or(PredictorError::NoRound)?;
+    let options = session.predictions(&backend.unwrap_or_default())?;
+    // Only remember them if the hand has not moved on in the meantime
+    if let Some(current) = state.lock().as_mut()
+        && current.stages.len() == session.stages.len()
+        && current.hand == session.hand
+    {
+        current.record_predictions(options.clone());
+    }
+    Ok(options)
+}
+
+#[tauri::command]
+fn end_round(
+    state: tauri::State<'_, CurrentRound>,
+    history: tauri::State<'_, History>,
+) -> Result<RoundRecord, PredictorError> {
+    let mut current = state.lock();
+    let session = current.as_ref().ok_or(PredictorError::NoRound)?;
+    let mut round = RoundRecord::from_session(session);
+    // The round stays open if it could not be saved, so ending it can be retried
+    round.id = Some(history.record(&round, &session.draw_outcomes()?)?);
+    *current = None;
+    Ok(round)
+}
+
+#[tauri::command]
+fn list_rounds(history: tauri::State<'_, History>, filter: Option<HistoryFilter>) -> Result<Vec<RoundRecord>, String> {
+    history.list(&filter.unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_round(history: tauri::State<'_, History>, id: i64) -> Result<bool, String> {
+    history.delete(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command(async)]
+fn calibration_report(history: tauri::State<'_, History>) -> Result<CalibrationReport, String> {
+    let log = history.prediction_log().map_err(|e| e.to_string())?;
+    Ok(CalibrationReport::new(&log))
+}
+
+/// The reliability table of the calibration report as CSV, for the frontend to save.
+#[tauri::command(async)]
+fn calibration_csv(history: tauri::State<'_, History>) -> Result<String, String> {
+    let log = history.prediction_log().map_err(|e| e.to_string())?;
+    Ok(CalibrationReport::new(&log).to_csv())
 }
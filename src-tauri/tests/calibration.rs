@@ -0,0 +1,87 @@
+mod test {
+    use balatro_predictor_lib::algorithm::calibration::{CalibrationReport, PredictionOutcome, BUCKETS};
+    use balatro_predictor_lib::algorithm::card_data::CardData;
+    use balatro_predictor_lib::algorithm::deck::Deck;
+    use balatro_predictor_lib::algorithm::distribution::OutcomeDistribution;
+    use balatro_predictor_lib::algorithm::exact::enumerate_draws;
+    use balatro_predictor_lib::algorithm::hand_rank::HandRank;
+    use balatro_predictor_lib::algorithm::notation::Hand;
+    use balatro_predictor_lib::algorithm::rules::RuleSet;
+    use balatro_predictor_lib::algorithm::session::RoundSession;
+    use balatro_predictor_lib::history::{History, RoundRecord};
+
+    fn cards(text: &str) -> Vec<CardData> {
+        text.parse::<Hand>().unwrap().into()
+    }
+
+    /// A prediction that gives `pair` to a Pair and the rest to High Card.
+    fn outcome(pair: f64, actual: HandRank) -> PredictionOutcome {
+        let mut probabilities = [0.0; HandRank::COUNT];
+        probabilities[HandRank::Pair.index()] = pair;
+        probabilities[HandRank::HighCard.index()] = 1.0 - pair;
+        PredictionOutcome {
+            distribution: OutcomeDistribution::from_probabilities(probabilities, 1),
+            actual,
+        }
+    }
+
+    #[test]
+    fn test_perfect_predictions() {
+        let log = vec![outcome(1.0, HandRank::Pair), outcome(0.0, HandRank::HighCard)];
+        let report = CalibrationReport::new(&log);
+        assert_eq!(report.predictions, 2);
+        assert_eq!(report.brier_score, 0.0);
+        assert!(report.log_loss < 1e-9);
+        assert!(report.ranks.iter().all(|rank| rank.bias == 0.0));
+    }
+
+    #[test]
+    fn test_overconfident_predictions() {
+        // A Pair is called at 90% but only happens half the time
+        let log: Vec<PredictionOutcome> = (0..10)
+            .map(|i| outcome(0.9, if i % 2 == 0 { HandRank::Pair } else { HandRank::HighCard }))
+            .collect();
+        let report = CalibrationReport::new(&log);
+
+        // Each miss costs 0.9² twice and each hit 0.1² twice
+        assert!((report.brier_score - 0.82).abs() < 1e-6);
+        assert!((report.log_loss - (-(0.9f64.ln() + 0.1f64.ln()) / 2.0)).abs() < 1e-6);
+
+        let pair = &report.ranks[HandRank::Pair.index()];
+        assert!((pair.bias - 0.4).abs() < 1e-6);
+        assert!((pair.expected_calibration_error - 0.4).abs() < 1e-6);
+        let bucket = &pair.buckets[9];
+        assert_eq!(bucket.count, 10);
+        assert!((bucket.observed - 0.5).abs() < 1e-9);
+        assert!(bucket.bias > 0.0);
+        assert!(report.ranks[HandRank::HighCard.index()].bias < 0.0);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 1 + HandRank::COUNT * BUCKETS);
+        assert!(csv.starts_with("rank,lower,upper,count,mean_predicted,observed,bias\n"));
+        assert!(csv.contains("Pair,0.9,1.0,10,0.900000,0.500000,0.400000"));
+    }
+
+    #[test]
+    fn test_history_logs_each_draw() {
+        let deck = Deck::from_changes(&["remove c".parse().unwrap(), "remove d".parse().unwrap()]);
+        let mut session = RoundSession::start(cards("2h 7h 9s Ks Kh"), None, deck, RuleSet::DST).unwrap();
+        session.apply_discard(cards("2h 7h 9s"), cards("Qs Js Ts")).unwrap();
+        // Throwing the pair away afterwards does not make the first draw look wrong
+        session.apply_discard(cards("Ks Kh"), cards("3h 4h")).unwrap();
+
+        let history = History::in_memory().unwrap();
+        let round = RoundRecord::from_session(&session);
+        assert_eq!(round.rank, HandRank::HighCard);
+        let outcomes = session.draw_outcomes().unwrap();
+        history.record(&round, &outcomes).unwrap();
+
+        let log = history.prediction_log().unwrap();
+        assert_eq!(log, outcomes);
+        assert_eq!(log.len(), 2);
+        let first = enumerate_draws(&cards("Ks Kh"), &cards("2h 7h 9s"), &[], &session.deck, None, &session.rules).unwrap();
+        assert_eq!(log[0].distribution, first.distribution());
+        assert_eq!(log[0].actual, HandRank::Pair);
+        assert_eq!(log[1].actual, HandRank::HighCard);
+    }
+}
@@ -0,0 +1,84 @@
+mod test {
+    use balatro_predictor_lib::algorithm::balatro_predictor::PredictionBackend;
+    use balatro_predictor_lib::algorithm::card_data::CardData;
+    use balatro_predictor_lib::algorithm::deck::Deck;
+    use balatro_predictor_lib::algorithm::hand_rank::HandRank;
+    use balatro_predictor_lib::algorithm::joker::Joker;
+    use balatro_predictor_lib::algorithm::notation::Hand;
+    use balatro_predictor_lib::algorithm::rules::RuleSet;
+    use balatro_predictor_lib::algorithm::session::RoundSession;
+    use balatro_predictor_lib::history::{History, HistoryFilter, RoundRecord};
+
+    fn cards(text: &str) -> Vec<CardData> {
+        text.parse::<Hand>().unwrap().into()
+    }
+
+    fn round(hand: &str, joker: Option<Joker>, played_at: i64) -> RoundRecord {
+        let session = RoundSession::start(cards(hand), joker, Deck::standard(), RuleSet::DST).unwrap();
+        RoundRecord {
+            played_at,
+            ..RoundRecord::from_session(&session)
+        }
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        let rules = RuleSet {
+            discards: 1,
+            ..RuleSet::DST
+        };
+        let mut session = RoundSession::start(cards("2c 7d 9h Ks Kd"), Some(Joker::Wilson), Deck::standard(), rules).unwrap();
+        session.apply_discard(cards("2c 7d 9h"), cards("Kh 3s 4s")).unwrap();
+        let options = session.predictions(&PredictionBackend::Exact).unwrap();
+        session.record_predictions(options);
+
+        let history = History::in_memory().unwrap();
+        let mut recorded = RoundRecord::from_session(&session);
+        assert_eq!(recorded.rank, HandRank::ThreeOfAKind);
+        recorded.id = Some(history.record(&recorded, &[]).unwrap());
+
+        let rounds = history.list(&HistoryFilter::default()).unwrap();
+        assert_eq!(rounds, vec![recorded]);
+        assert_eq!(rounds[0].stages[0].drawn, cards("Kh 3s 4s"));
+        assert_eq!(rounds[0].predictions[0].stage, 1);
+    }
+
+    #[test]
+    fn test_filter_and_delete() {
+        let history = History::in_memory().unwrap();
+        let pair = history.record(&round("2c 2d 9h Js Ks", Some(Joker::Woodie), 100), &[]).unwrap();
+        let flush = history.record(&round("2h 5h 9h Jh Kh", Some(Joker::Wilson), 200), &[]).unwrap();
+        let high = history.record(&round("2c 5d 9h Js Kc", None, 300), &[]).unwrap();
+
+        let ids = |filter: HistoryFilter| -> Vec<i64> {
+            history.list(&filter).unwrap().iter().map(|round| round.id.unwrap()).collect()
+        };
+        assert_eq!(ids(HistoryFilter::default()), vec![high, flush, pair]);
+        assert_eq!(
+            ids(HistoryFilter {
+                joker: Some(Joker::Woodie),
+                ..HistoryFilter::default()
+            }),
+            vec![pair]
+        );
+        assert_eq!(
+            ids(HistoryFilter {
+                rank: Some(HandRank::Flush),
+                ..HistoryFilter::default()
+            }),
+            vec![flush]
+        );
+        assert_eq!(
+            ids(HistoryFilter {
+                from: Some(150),
+                to: Some(300),
+                ..HistoryFilter::default()
+            }),
+            vec![high, flush]
+        );
+
+        assert!(history.delete(flush).unwrap());
+        assert!(!history.delete(flush).unwrap());
+        assert_eq!(ids(HistoryFilter::default()), vec![high, pair]);
+    }
+}
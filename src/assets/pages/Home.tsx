@@ -1,17 +1,17 @@
 import {useState} from "react";
 import {Jokers, JokerSelector} from "../components/JokerSelector.tsx";
-import {Button, Select, SelectItem} from "@heroui/react";
+import {Button} from "@heroui/react";
 import {CardOptionSelector} from "../components/CardOptionSelector.tsx";
 import {RemainingDiscardsToggle} from "../components/RemainingDiscardsToggle.tsx";
-import {CalculationType, Card, CardCommands, CardFace, CardSuit} from "../ts/CardCommands.ts";
+import {Card, CardCommands, CardFace, CardSuit, HandRank, HandRankNames, OutcomeDistribution} from "../ts/CardCommands.ts";
 
 export default function Home()
 {
     const [joker, setJoker] = useState(Jokers[0]);
     const [cards, setCards] = useState<Card[]>(Array(5).fill({card_face: CardFace.Ace, card_suit: CardSuit.Clubs} as Card));
-    const [calculationType, setCalculationType] = useState<CalculationType>(CalculationType.HighCard);
     const [remainingDiscards, setRemainingDiscards] = useState<boolean>(true);
-    const [result, setResult] = useState<number | null>(null);
+    const [result, setResult] = useState<OutcomeDistribution | null>(null);
+    const [expectedScore, setExpectedScore] = useState<number | null>(null);
 
     const handleCardChange = (index: number, newCard: CardOption) => {
         const newCards = [...cards];
@@ -26,32 +26,22 @@ export default function Home()
             card_suit: card.card_suit.toString()
         }));
 
-        // Call the appropriate calculation function
-        const probability = await CardCommands.calculateProbability(
-            calculationType,
-            formattedCards,
-            [] // No discarded cards for now
-        );
+        // The round keeps track of what has left the deck
+        const session = await CardCommands.startRound(formattedCards, joker.name);
+        if (session === null) return;
+        const discardedCards = CardCommands.discardedCards(session);
 
-        setResult(probability);
+        // Every hand type comes back from a single call
+        const distribution = await CardCommands.calculateOutcomeDistribution(session.hand, discardedCards, []);
+
+        setResult(distribution);
+        setExpectedScore(await CardCommands.calculateExpectedScore(session.hand, discardedCards, [], joker.name));
     };
 
     return (
         <div className={"flex flex-col gap-2"}>
             <JokerSelector value={joker} onChange={setJoker}/>
 
-            <Select 
-                label="Calculation Type" 
-                selectedKeys={[calculationType]}
-                onSelectionChange={(keys) => setCalculationType([...keys][0] as CalculationType)}
-            >
-                {Object.values(CalculationType).map((type) => (
-                    <SelectItem key={type} value={type}>
-                        {type}
-                    </SelectItem>
-                ))}
-            </Select>
-
             {Array.from({length: 5}, (_, i) => i).map(i =>
                 <CardOptionSelector 
                     key={i.toString()} 
@@ -76,7 +66,16 @@ export default function Home()
 
             {result !== null && (
                 <div className="mt-4 p-4 bg-gray-100 rounded-md">
-                    <p className="text-lg font-semibold">Probability: {(result * 100).toFixed(2)}%</p>
+                    {Object.values(HandRank).map(rank =>
+                        <p key={rank} className="text-lg font-semibold">
+                            {HandRankNames[rank]}: {((result.probabilities[rank] ?? 0) * 100).toFixed(2)}%
+                        </p>
+                    )}
+                    {expectedScore !== null && (
+                        <p className="text-lg font-semibold">
+                            Expected score with {joker.name}: {expectedScore.toFixed(1)}
+                        </p>
+                    )}
                 </div>
             )}
         </div>
@@ -3,8 +3,17 @@ import {invoke} from "@tauri-apps/api/core";
 export type Card = {
     card_face: CardFace;
     card_suit: CardSuit;
+    enhancement?: Enhancement;
+    edition?: Edition;
+    seal?: Seal;
 };
 
+export type Enhancement = "Bonus" | "Mult" | "Wild" | "Glass" | "Steel" | "Stone" | "Gold" | "Lucky";
+
+export type Edition = "Foil" | "Holographic" | "Polychrome";
+
+export type Seal = "Red" | "Blue" | "Gold" | "Purple";
+
 export enum CardSuit
 {
     Clubs = "Clubs",
@@ -30,220 +39,431 @@ export enum CardFace
     Ace = "Ace",
 }
 
-export enum CalculationType
+export enum HandRank
 {
-    HighCard = "High Card",
+    HighCard = "HighCard",
     Pair = "Pair",
-    TwoPair = "Two Pair",
-    ThreeOfAKind = "Three of a Kind",
+    TwoPair = "TwoPair",
+    ThreeOfAKind = "ThreeOfAKind",
     Straight = "Straight",
     Flush = "Flush",
-    FullHouse = "Full House",
-    FourOfAKind = "Four of a Kind",
-    StraightFlush = "Straight Flush",
-    RoyalFlush = "Royal Flush"
+    FullHouse = "FullHouse",
+    FourOfAKind = "FourOfAKind",
+    StraightFlush = "StraightFlush",
+    RoyalFlush = "RoyalFlush"
 }
 
+export const HandRankNames: Record<HandRank, string> = {
+    [HandRank.HighCard]: "High Card",
+    [HandRank.Pair]: "Pair",
+    [HandRank.TwoPair]: "Two Pair",
+    [HandRank.ThreeOfAKind]: "Three of a Kind",
+    [HandRank.Straight]: "Straight",
+    [HandRank.Flush]: "Flush",
+    [HandRank.FullHouse]: "Full House",
+    [HandRank.FourOfAKind]: "Four of a Kind",
+    [HandRank.StraightFlush]: "Straight Flush",
+    [HandRank.RoyalFlush]: "Royal Flush"
+};
+
+export type OutcomeDistribution = {
+    probabilities: Partial<Record<HandRank, number>>;
+    draws: number;
+};
+
+export type DiscardOption = {
+    discard: Card[];
+    kept: Card[];
+    distribution: OutcomeDistribution;
+    expected_score: number;
+    /** Draws the option was sampled over, null when it was counted exactly. */
+    samples?: number | null;
+};
+
+export type MonteCarloConfig = {
+    seed?: number;
+    max_samples?: number;
+    tolerance?: number;
+    inner_samples?: number;
+};
+
+export type PredictionBackend = { type: "Exact" } | ({ type: "MonteCarlo" } & MonteCarloConfig);
+
+export type Estimate = {
+    mean: number;
+    lower: number;
+    upper: number;
+    samples: number;
+};
+
+export type SampledOutcome = {
+    distribution: OutcomeDistribution;
+    probabilities: Partial<Record<HandRank, Estimate>>;
+    expected_score: Estimate;
+    samples: number;
+};
+
+export type Score = {
+    chips: number;
+    mult: number;
+    total: number;
+};
+
+/** A deck listed one entry per copy, so a card may appear more than once. */
+export type Deck = Card[];
+
+export type DeckChange =
+    | { type: "RemoveFace"; face: CardFace }
+    | { type: "RemoveSuit"; suit: CardSuit }
+    | { type: "RemoveCard"; card: Card }
+    | { type: "AddCard"; card: Card; copies: number };
+
+export type RuleSet = {
+    hand_size?: number;
+    discards?: number;
+    max_discard_size?: number;
+    mult?: Partial<Record<HandRank, number>>;
+    base_chips?: Partial<Record<HandRank, number>>;
+    chips?: Partial<Record<CardFace, number>>;
+    ace_low?: boolean;
+};
+
+/** What a predictor command rejects with, `cards` being the cards that caused it. */
+export type PredictorError = {
+    code:
+        | "hand_size"
+        | "duplicate_card"
+        | "discarded_in_hand"
+        | "missing_from_deck"
+        | "not_in_hand"
+        | "discard_size"
+        | "discard_count"
+        | "not_enough_cards"
+        | "draw_count"
+        | "invalid_rules"
+        | "other";
+    message: string;
+    cards: Card[];
+};
+
+export type DiscardStage = {
+    discarded: Card[];
+    drawn: Card[];
+};
+
+export type RoundSession = {
+    hand: Card[];
+    starting_hand: Card[];
+    stages: DiscardStage[];
+    seen: Card[];
+    discards_remaining: number;
+    joker?: string;
+    deck: Deck;
+    rules: RuleSet;
+    predictions: ShownPredictions[];
+};
+
+export type ShownPredictions = {
+    stage: number;
+    options: DiscardOption[];
+};
+
+export type RoundRecord = {
+    id?: number;
+    /** Seconds since the Unix epoch. */
+    played_at: number;
+    joker?: string;
+    starting_hand: Card[];
+    stages: DiscardStage[];
+    final_hand: Card[];
+    rank: HandRank;
+    score: Score;
+    predictions: ShownPredictions[];
+};
+
+export type HistoryFilter = {
+    joker?: string;
+    rank?: HandRank;
+    from?: number;
+    to?: number;
+};
+
+export type ReliabilityBucket = {
+    lower: number;
+    upper: number;
+    count: number;
+    mean_predicted: number;
+    observed: number;
+    /** Positive when these predictions were overconfident, negative when underconfident. */
+    bias: number;
+};
+
+export type RankCalibration = {
+    rank: HandRank;
+    mean_predicted: number;
+    observed: number;
+    bias: number;
+    expected_calibration_error: number;
+    buckets: ReliabilityBucket[];
+};
+
+export type CalibrationReport = {
+    predictions: number;
+    brier_score: number;
+    log_loss: number;
+    ranks: RankCalibration[];
+};
+
+export type HandEvaluation = {
+    rank: HandRank;
+    scoring_cards: Card[];
+    kickers: Card[];
+    key: number;
+};
+
 export class CardCommands
 {
-    public static async calculateHighCardProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async evaluateHand(hand: Card[], rules?: RuleSet): Promise<HandEvaluation | null>
     {
-        console.log("Calculating high card probability for", hand, discardedCards);
         try
         {
-            const probability = await invoke<number>("calculate_high_card_probability", {
-                hand,
-                discardedCards
-            });
-            console.log("High Card Probability:", probability);
-            return probability;
+            return await invoke<HandEvaluation>("evaluate_hand", {hand, rules});
         } catch (error)
         {
-            console.error("Error calculating high card probability:", error);
-            return 0;
+            console.error("Error evaluating hand:", error);
+            return null;
         }
     }
 
-    public static async calculatePairProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async parseHand(text: string): Promise<Card[]>
+    {
+        return await invoke<Card[]>("parse_hand", {text});
+    }
+
+    public static async scoreHand(hand: Card[], joker?: string, rules?: RuleSet): Promise<Score | null>
     {
         try
         {
-            const probability = await invoke<number>("calculate_pair_probability", {
-                hand,
-                discardedCards
-            });
-            console.log("Pair Probability:", probability);
-            return probability;
+            return await invoke<Score>("score_hand", {hand, joker, rules});
         } catch (error)
         {
-            console.error("Error calculating pair probability:", error);
-            return 0;
+            console.error("Error scoring hand:", error);
+            return null;
         }
     }
 
-    public static async calculateTwoPairProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async calculateExpectedScore(hand: Card[], discardedCards: Card[] = [], discard: Card[] = [], joker?: string, backend?: PredictionBackend, deck?: Deck, rules?: RuleSet): Promise<number>
     {
         try
         {
-            const probability = await invoke<number>("calculate_two_pair_probability", {
+            const expectedScore = await invoke<number>("calculate_expected_score", {
                 hand,
-                discardedCards
+                discardedCards,
+                discard,
+                joker,
+                backend,
+                deck,
+                rules
             });
-            console.log("Two Pair Probability:", probability);
-            return probability;
+            console.log("Expected Score:", expectedScore);
+            return expectedScore;
         } catch (error)
         {
-            console.error("Error calculating two pair probability:", error);
+            console.error("Error calculating expected score:", error);
             return 0;
         }
     }
 
-    public static async calculateThreeOfAKindProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async calculateOutcomeDistribution(hand: Card[], discardedCards: Card[] = [], discard: Card[] = [], backend?: PredictionBackend, deck?: Deck, rules?: RuleSet): Promise<OutcomeDistribution | null>
     {
         try
         {
-            const probability = await invoke<number>("calculate_three_of_a_kind_probability", {
+            const distribution = await invoke<OutcomeDistribution>("calculate_outcome_distribution", {
                 hand,
-                discardedCards
+                discardedCards,
+                discard,
+                backend,
+                deck,
+                rules
             });
-            console.log("Three of a Kind Probability:", probability);
-            return probability;
+            console.log("Outcome Distribution:", distribution);
+            return distribution;
         } catch (error)
         {
-            console.error("Error calculating three of a kind probability:", error);
-            return 0;
+            console.error("Error calculating outcome distribution:", error);
+            return null;
         }
     }
 
-    public static async calculateStraightProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async optimizeDiscard(hand: Card[], discardedCards: Card[] = [], joker?: string, backend?: PredictionBackend, deck?: Deck, rules?: RuleSet): Promise<DiscardOption[]>
     {
         try
         {
-            const probability = await invoke<number>("calculate_straight_probability", {
+            const options = await invoke<DiscardOption[]>("optimize_discard", {
                 hand,
-                discardedCards
+                discardedCards,
+                joker,
+                backend,
+                deck,
+                rules
             });
-            console.log("Straight Probability:", probability);
-            return probability;
+            console.log("Discard Options:", options);
+            return options;
         } catch (error)
         {
-            console.error("Error calculating straight probability:", error);
-            return 0;
+            console.error("Error optimizing discard:", error);
+            return [];
         }
     }
 
-    public static async calculateFlushProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async planDiscards(hand: Card[], discardedCards: Card[] = [], discardsRemaining: number = 2, joker?: string, backend?: PredictionBackend, deck?: Deck, rules?: RuleSet): Promise<DiscardOption[]>
     {
         try
         {
-            const probability = await invoke<number>("calculate_flush_probability", {
+            const options = await invoke<DiscardOption[]>("plan_discards", {
                 hand,
-                discardedCards
+                discardedCards,
+                discardsRemaining,
+                joker,
+                backend,
+                deck,
+                rules
             });
-            console.log("Flush Probability:", probability);
-            return probability;
+            console.log("Planned Discards:", options);
+            return options;
         } catch (error)
         {
-            console.error("Error calculating flush probability:", error);
-            return 0;
+            console.error("Error planning discards:", error);
+            return [];
         }
     }
 
-    public static async calculateFullHouseProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async simulateOutcome(hand: Card[], discardedCards: Card[] = [], discard: Card[] = [], discardsAfter: number = 0, joker?: string, config?: MonteCarloConfig, deck?: Deck, rules?: RuleSet): Promise<SampledOutcome | null>
     {
         try
         {
-            const probability = await invoke<number>("calculate_full_house_probability", {
+            const outcome = await invoke<SampledOutcome>("simulate_outcome", {
                 hand,
-                discardedCards
+                discardedCards,
+                discard,
+                discardsAfter,
+                joker,
+                config,
+                deck,
+                rules
             });
-            console.log("Full House Probability:", probability);
-            return probability;
+            console.log("Sampled Outcome:", outcome);
+            return outcome;
         } catch (error)
         {
-            console.error("Error calculating full house probability:", error);
-            return 0;
+            console.error("Error simulating outcome:", error);
+            return null;
         }
     }
 
-    public static async calculateFourOfAKindProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async buildDeck(changes: DeckChange[]): Promise<Deck>
+    {
+        return await invoke<Deck>("build_deck", {changes});
+    }
+
+    public static async ruleSetPresets(): Promise<[string, RuleSet][]>
+    {
+        return await invoke<[string, RuleSet][]>("rule_set_presets");
+    }
+
+    /** Reads a `.toml` or `.json` rule set by file name from the app's config folder. */
+    public static async loadRuleSet(name: string): Promise<RuleSet>
+    {
+        return await invoke<RuleSet>("load_rule_set", {name});
+    }
+
+    /** Every card thrown away so far in the round. */
+    public static discardedCards(session: RoundSession): Card[]
+    {
+        return session.stages.flatMap(stage => stage.discarded);
+    }
+
+    public static async startRound(hand: Card[], joker?: string, deck?: Deck, rules?: RuleSet): Promise<RoundSession | null>
     {
         try
         {
-            const probability = await invoke<number>("calculate_four_of_a_kind_probability", {
-                hand,
-                discardedCards
-            });
-            console.log("Four of a Kind Probability:", probability);
-            return probability;
+            return await invoke<RoundSession>("start_round", {hand, joker, deck, rules});
         } catch (error)
         {
-            console.error("Error calculating four of a kind probability:", error);
-            return 0;
+            console.error("Error starting round:", error);
+            return null;
         }
     }
 
-    public static async calculateStraightFlushProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async applyDiscard(cards: Card[], drawn: Card[]): Promise<RoundSession | null>
     {
         try
         {
-            const probability = await invoke<number>("calculate_straight_flush_probability", {
-                hand,
-                discardedCards
-            });
-            console.log("Straight Flush Probability:", probability);
-            return probability;
+            return await invoke<RoundSession>("apply_discard", {cards, drawn});
         } catch (error)
         {
-            console.error("Error calculating straight flush probability:", error);
-            return 0;
+            console.error("Error applying discard:", error);
+            return null;
         }
     }
 
-    public static async calculateRoyalFlushProbability(hand: Card[], discardedCards: Card[] = []): Promise<number>
+    public static async currentPredictions(backend?: PredictionBackend): Promise<DiscardOption[]>
     {
         try
         {
-            const probability = await invoke<number>("calculate_royal_flush_probability", {
-                hand,
-                discardedCards
-            });
-            console.log("Royal Flush Probability:", probability);
-            return probability;
+            const options = await invoke<DiscardOption[]>("current_predictions", {backend});
+            console.log("Current Predictions:", options);
+            return options;
         } catch (error)
         {
-            console.error("Error calculating royal flush probability:", error);
-            return 0;
+            console.error("Error predicting the current round:", error);
+            return [];
         }
     }
 
-    public static async calculateProbability(type: CalculationType, hand: Card[], discardedCards: Card[] = []): Promise<number>
-    {
-        switch (type)
-        {
-            case CalculationType.HighCard:
-                return this.calculateHighCardProbability(hand, discardedCards);
-            case CalculationType.Pair:
-                return this.calculatePairProbability(hand, discardedCards);
-            case CalculationType.TwoPair:
-                return this.calculateTwoPairProbability(hand, discardedCards);
-            case CalculationType.ThreeOfAKind:
-                return this.calculateThreeOfAKindProbability(hand, discardedCards);
-            case CalculationType.Straight:
-                return this.calculateStraightProbability(hand, discardedCards);
-            case CalculationType.Flush:
-                return this.calculateFlushProbability(hand, discardedCards);
-            case CalculationType.FullHouse:
-                return this.calculateFullHouseProbability(hand, discardedCards);
-            case CalculationType.FourOfAKind:
-                return this.calculateFourOfAKindProbability(hand, discardedCards);
-            case CalculationType.StraightFlush:
-                return this.calculateStraightFlushProbability(hand, discardedCards);
-            case CalculationType.RoyalFlush:
-                return this.calculateRoyalFlushProbability(hand, discardedCards);
-            default:
-                console.error("Unknown calculation type:", type);
-                return 0;
+    public static async endRound(): Promise<RoundRecord | null>
+    {
+        try
+        {
+            return await invoke<RoundRecord>("end_round");
+        } catch (error)
+        {
+            console.error("Error ending round:", error);
+            return null;
         }
     }
+
+    public static async listRounds(filter?: HistoryFilter): Promise<RoundRecord[]>
+    {
+        try
+        {
+            return await invoke<RoundRecord[]>("list_rounds", {filter});
+        } catch (error)
+        {
+            console.error("Error listing rounds:", error);
+            return [];
+        }
+    }
+
+    public static async deleteRound(id: number): Promise<boolean>
+    {
+        return await invoke<boolean>("delete_round", {id});
+    }
+
+    public static async calibrationReport(): Promise<CalibrationReport | null>
+    {
+        try
+        {
+            return await invoke<CalibrationReport>("calibration_report");
+        } catch (error)
+        {
+            console.error("Error building calibration report:", error);
+            return null;
+        }
+    }
+
+    public static async calibrationCsv(): Promise<string>
+    {
+        return await invoke<string>("calibration_csv");
+    }
 }